@@ -1,13 +1,27 @@
-use std::ops::Deref;
+//! The [Choice] type and helpers for working with collections of choices inside a chooser.
+
+use std::cmp::Ordering;
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub(crate) struct Guard;
 
 /// A specific choice, passed to closure by [`Selector::with`](crate::Selector::with) or [`SelectorFixed::with`](crate::SelectorFixed::with).
+///
+/// A choice doesn't know where its value came from. Selections that need positions hand
+/// out [IndexedChoice]s instead.
 #[derive(Debug)]
-#[repr(transparent)]
+// the index is only there for the debug post-conditions, release builds keep Choice a
+// transparent wrapper so collecting the values back out doesn't need a new allocation
+#[cfg_attr(not(debug_assertions), repr(transparent))]
 pub struct Choice<'guard, T> {
     value: T,
+    #[cfg(debug_assertions)]
+    index: usize,
+    // only ties the choice to the lifetime of its guard. Guard is Send and Sync, so this never
+    // changes the auto traits of a choice, those follow T
     _guard: std::marker::PhantomData<&'guard Guard>,
 }
 
@@ -21,9 +35,14 @@ impl<'a, T> Deref for Choice<'a, T> {
 }
 
 impl<'guard, T> Choice<'guard, T> {
-    pub(crate) fn with_guard(value: T, _guard: &'guard Guard) -> Choice<'guard, T> {
+    pub(crate) fn with_guard(value: T, index: usize, _guard: &'guard Guard) -> Choice<'guard, T> {
+        #[cfg(not(debug_assertions))]
+        let _ = index;
+
         Choice {
             value,
+            #[cfg(debug_assertions)]
+            index,
             _guard: std::marker::PhantomData,
         }
     }
@@ -31,6 +50,495 @@ impl<'guard, T> Choice<'guard, T> {
     pub(crate) fn into_inner(self) -> T {
         self.value
     }
+
+    #[cfg(debug_assertions)]
+    pub(crate) fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Keeps this choice only if its value satisfies `pred`, similar to [Option::filter].
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let chosen = select_from(vec![1, 2, 3]).any_with(|choices| {
+    ///     // narrow the last choice down, if it is odd
+    ///     choices.into_iter().last().and_then(|c| c.filter(|n| n % 2 == 1)).into_iter().collect()
+    /// });
+    ///
+    /// assert_eq!(chosen, [3]);
+    /// ```
+    pub fn filter<P>(self, pred: P) -> Option<Choice<'guard, T>>
+    where
+        P: FnOnce(&T) -> bool,
+    {
+        if pred(&self) {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    /// Projects a borrowed view out of the value, without consuming the choice. Only
+    /// real choices can be returned from a chooser, so the view is just for inspection:
+    /// ```compile_fail
+    /// use choose_from::select_from;
+    ///
+    /// struct Card {
+    ///     name: String,
+    /// }
+    ///
+    /// let cards = vec![Card { name: "Ace".to_string() }];
+    /// // a view is not a Choice, so it can't be part of the selection
+    /// let [name] = select_from(cards).with(|choices| [choices[0].map_ref(|c| &c.name)]);
+    /// ```
+    /// Instead decide based on the view and return the original choice:
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// struct Card {
+    ///     name: String,
+    ///     cost: u32,
+    /// }
+    ///
+    /// let cards = vec![
+    ///     Card { name: "Ace".to_string(), cost: 3 },
+    ///     Card { name: "King".to_string(), cost: 1 },
+    /// ];
+    ///
+    /// let [cheapest] = select_from(cards).with(|choices| {
+    ///     let index = if choices[0].map_ref(|c| &c.cost) < choices[1].map_ref(|c| &c.cost) {
+    ///         0
+    ///     } else {
+    ///         1
+    ///     };
+    ///     [choices.into_iter().nth(index).unwrap()]
+    /// });
+    ///
+    /// assert_eq!(cheapest.name, "King");
+    /// ```
+    pub fn map_ref<'a, U, F>(&'a self, f: F) -> &'a U
+    where
+        U: ?Sized,
+        F: FnOnce(&'a T) -> &'a U,
+    {
+        f(self)
+    }
+}
+
+impl<'guard, T> Choice<'guard, Arc<T>> {
+    /// Clones the inner [Arc] for bookkeeping, without creating a new [Choice].
+    ///
+    /// The clone is just another handle to the shared value, it cannot be returned
+    /// from a chooser in place of a [Choice], so the selection is still exactly the
+    /// choices the chooser returns.
+    /// ```
+    /// use std::sync::Arc;
+    /// use choose_from::select_from_shared;
+    ///
+    /// let pool = [Arc::new("fire"), Arc::new("ice")];
+    /// let mut seen = Vec::new();
+    ///
+    /// let [chosen] = select_from_shared(&pool).with(|mut choices| {
+    ///     seen.extend(choices.iter().map(|c| c.share()));
+    ///     [choices.remove(1)]
+    /// });
+    ///
+    /// assert_eq!(seen.len(), 2);
+    /// assert!(Arc::ptr_eq(&chosen, &pool[1]));
+    /// ```
+    pub fn share(&self) -> Arc<T> {
+        Arc::clone(self)
+    }
+}
+
+impl<'guard, T> Choice<'guard, Rc<T>> {
+    /// Clones the inner [Rc] for bookkeeping, without creating a new [Choice].
+    /// See the `Arc` version for details.
+    pub fn share(&self) -> Rc<T> {
+        Rc::clone(self)
+    }
+}
+
+/// A choice paired with a weight, as presented by
+/// [select_from_weighted](crate::select_from_weighted). The weight can be read but not
+/// changed, since it is part of the chosen value.
+///
+/// Only weighted selections hand these out, so a plain choice of a pair has no weight:
+/// ```compile_fail
+/// use choose_from::select_from;
+///
+/// let users = vec![(1, "ann"), (2, "bob")];
+///
+/// select_from(users).any_with(|choices| {
+///     let _ = choices[0].weight();
+///     choices
+/// });
+/// ```
+#[derive(Debug)]
+pub struct WeightedChoice<'guard, T, W> {
+    choice: Choice<'guard, (T, W)>,
+}
+
+impl<'guard, T, W> WeightedChoice<'guard, T, W> {
+    pub(crate) fn new(choice: Choice<'guard, (T, W)>) -> WeightedChoice<'guard, T, W> {
+        WeightedChoice { choice }
+    }
+
+    pub(crate) fn into_choice(self) -> Choice<'guard, (T, W)> {
+        self.choice
+    }
+
+    /// The value of this weighted choice.
+    pub fn value(&self) -> &T {
+        &self.choice.0
+    }
+
+    /// The weight paired with the value of this choice.
+    pub fn weight(&self) -> &W {
+        &self.choice.1
+    }
+}
+
+/// Keeps only the choices whose values satisfy `pred`, preserving their order.
+/// ```
+/// use choose_from::{choice, select_from};
+///
+/// let chosen = select_from(1..=6).any_with(|choices| choice::retain(choices, |n| n % 2 == 0));
+///
+/// assert_eq!(chosen, [2, 4, 6]);
+/// ```
+pub fn retain<'guard, T, P>(
+    mut choices: Vec<Choice<'guard, T>>,
+    mut pred: P,
+) -> Vec<Choice<'guard, T>>
+where
+    P: FnMut(&T) -> bool,
+{
+    choices.retain(|c| pred(c));
+    choices
+}
+
+/// Sorts the choices by a key extracted from their values, see [slice::sort_by_key].
+/// ```
+/// use choose_from::{choice, select_from};
+///
+/// let chosen = select_from(["ccc", "a", "bb"]).with(|mut choices| {
+///     choice::sort_by_key(&mut choices, |s| s.len());
+///     let mut choices = choices.into_iter();
+///
+///     [choices.next().unwrap(), choices.next().unwrap()]
+/// });
+///
+/// assert_eq!(chosen, ["a", "bb"]);
+/// ```
+pub fn sort_by_key<T, K, F>(choices: &mut [Choice<'_, T>], mut f: F)
+where
+    F: FnMut(&T) -> K,
+    K: Ord,
+{
+    choices.sort_by_key(|c| f(c));
+}
+
+/// Sorts the choices with a comparator on their values, see [slice::sort_by].
+/// ```
+/// use choose_from::{choice, select_from};
+///
+/// let chosen = select_from([2, 3, 1]).any_with(|mut choices| {
+///     choice::sort_by(&mut choices, |a, b| b.cmp(a));
+///     choices
+/// });
+///
+/// assert_eq!(chosen, [3, 2, 1]);
+/// ```
+pub fn sort_by<T, F>(choices: &mut [Choice<'_, T>], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    choices.sort_by(|a, b| compare(a, b));
+}
+
+/// Returns the choice with the maximum key, see [Iterator::max_by_key]. If several
+/// choices are equally maximum, the last one is returned.
+/// ```
+/// use choose_from::{choice, select_from};
+///
+/// let [longest] = select_from(["ccc", "a", "bb"])
+///     .with(|choices| [choice::max_by_key(choices, |s| s.len()).unwrap()]);
+///
+/// assert_eq!(longest, "ccc");
+/// ```
+pub fn max_by_key<'guard, T, K, F, I>(choices: I, mut f: F) -> Option<Choice<'guard, T>>
+where
+    I: IntoIterator<Item = Choice<'guard, T>>,
+    F: FnMut(&T) -> K,
+    K: Ord,
+{
+    choices.into_iter().max_by_key(|c| f(c))
+}
+
+/// Takes the choice out of `slot` if there is one and its value satisfies `pred`,
+/// leaving `None` in its place. If the predicate fails the slot is left untouched.
+/// ```
+/// use choose_from::{choice, select_from};
+///
+/// let chosen = select_from(1..=4).any_with(|choices| {
+///     let mut slots: Vec<_> = choices.into_iter().map(Some).collect();
+///
+///     // take the even choices, the odd ones stay in their slots
+///     slots
+///         .iter_mut()
+///         .filter_map(|slot| choice::take_if(slot, |n| n % 2 == 0))
+///         .collect()
+/// });
+///
+/// assert_eq!(chosen, [2, 4]);
+/// ```
+pub fn take_if<'guard, T, P>(
+    slot: &mut Option<Choice<'guard, T>>,
+    pred: P,
+) -> Option<Choice<'guard, T>>
+where
+    P: FnOnce(&T) -> bool,
+{
+    match slot {
+        Some(choice) if pred(choice) => slot.take(),
+        _ => None,
+    }
+}
+
+/// A choice that knows its position, handed out by the selections that need to know where
+/// the chosen values came from, like [`Selector::with_indices`](crate::Selector::with_indices)
+/// or [`Selector::partition_with`](crate::Selector::partition_with). The values stay with
+/// the selector while the chooser runs, so these choices only borrow them, and the chosen
+/// values are taken out by position once the chooser returns.
+/// ```
+/// use choose_from::select_from;
+///
+/// let (chosen, rest) = select_from(vec!["a", "b", "c"]).partition_with(|choices| {
+///     choices.into_iter().filter(|c| c.index() != 1).collect()
+/// });
+///
+/// assert_eq!(chosen, ["a", "c"]);
+/// assert_eq!(rest, ["b"]);
+/// ```
+#[derive(Debug)]
+pub struct IndexedChoice<'guard, T> {
+    value: &'guard T,
+    index: usize,
+}
+
+impl<'guard, T> Deref for IndexedChoice<'guard, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.value
+    }
+}
+
+impl<'guard, T> IndexedChoice<'guard, T> {
+    /// The position of this choice's value among the choices of the selection, which stays
+    /// the same however the chooser reorders or filters the choices. Positions are counted
+    /// after adapters like [filter_choices](crate::Selector::filter_choices), so they are
+    /// only positions in the original values if the selector wasn't adapted.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// select_from(vec!["a", "b", "c"]).with_indices(|mut choices| {
+    ///     choices.reverse();
+    ///     assert_eq!(choices[0].index(), 2);
+    ///     [choices.remove(0)]
+    /// });
+    /// ```
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+// choices for every value of a slice, at their positions in it
+pub(crate) fn indexed<T>(values: &[T]) -> Vec<IndexedChoice<'_, T>> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| IndexedChoice { value, index })
+        .collect()
+}
+
+pub(crate) fn indexed_fixed<const N: usize, T>(values: &[T; N]) -> [IndexedChoice<'_, T>; N] {
+    std::array::from_fn(|index| IndexedChoice {
+        value: &values[index],
+        index,
+    })
+}
+
+/// The choices of a lazy selection, see [`Selector::with_iter`](crate::Selector::with_iter).
+/// Values are only pulled from the underlying iterator as the chooser asks for them, so
+/// whatever the chooser never reaches is never produced.
+#[derive(Debug)]
+pub struct LazyChoices<'guard, I> {
+    iter: std::iter::Enumerate<I>,
+    guard: &'guard Guard,
+}
+
+impl<'guard, I: Iterator> LazyChoices<'guard, I> {
+    pub(crate) fn new(iter: I, guard: &'guard Guard) -> LazyChoices<'guard, I> {
+        LazyChoices {
+            iter: iter.enumerate(),
+            guard,
+        }
+    }
+}
+
+impl<'guard, I> Iterator for LazyChoices<'guard, I>
+where
+    I: Iterator,
+    I::Item: 'guard,
+{
+    type Item = Choice<'guard, I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, value) = self.iter.next()?;
+        Some(Choice::with_guard(value, index, self.guard))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// The choices of a selection made through [`Selector::with_set`](crate::Selector::with_set)
+/// or [`Selector::any_with_set`](crate::Selector::any_with_set). It dereferences to the
+/// [Vec] of choices the other methods hand out, so code written for a [Vec] works as is.
+/// ```
+/// use choose_from::select_from;
+///
+/// let chosen = select_from((1..=20).filter(|n| n % 7 == 0)).any_with_set(|choices| {
+///     // the number of choices is known even though they came from an iterator
+///     let mut picked = Vec::with_capacity(choices.len());
+///     picked.extend(choices.into_iter().filter(|c| **c > 10));
+///     picked
+/// });
+///
+/// assert_eq!(chosen, [14]);
+/// ```
+#[derive(Debug)]
+pub struct ChoiceSet<'guard, T> {
+    choices: Vec<Choice<'guard, T>>,
+}
+
+impl<'guard, T> ChoiceSet<'guard, T> {
+    pub(crate) fn new(choices: Vec<Choice<'guard, T>>) -> ChoiceSet<'guard, T> {
+        ChoiceSet { choices }
+    }
+
+    /// The number of choices in the set.
+    pub fn len(&self) -> usize {
+        self.choices.len()
+    }
+
+    /// Whether there are no choices in the set.
+    pub fn is_empty(&self) -> bool {
+        self.choices.is_empty()
+    }
+
+    /// Unwraps the set into the [Vec] of choices.
+    pub fn into_vec(self) -> Vec<Choice<'guard, T>> {
+        self.choices
+    }
+}
+
+impl<'guard, T> Deref for ChoiceSet<'guard, T> {
+    type Target = Vec<Choice<'guard, T>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.choices
+    }
+}
+
+impl<'guard, T> DerefMut for ChoiceSet<'guard, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.choices
+    }
+}
+
+impl<'guard, T> IntoIterator for ChoiceSet<'guard, T> {
+    type Item = Choice<'guard, T>;
+    type IntoIter = std::vec::IntoIter<Choice<'guard, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.choices.into_iter()
+    }
+}
+
+impl<'guard, T> From<ChoiceSet<'guard, T>> for Vec<Choice<'guard, T>> {
+    fn from(set: ChoiceSet<'guard, T>) -> Self {
+        set.choices
+    }
+}
+
+/// Storage for selections that need the unchosen values back. The chooser is handed
+/// choices borrowing from the slots, and once it returns the chosen values are taken
+/// out by index, leaving the rest behind.
+pub(crate) struct Slots<T> {
+    values: Vec<Option<T>>,
+}
+
+impl<T> Slots<T> {
+    pub(crate) fn new<I>(values: I) -> Slots<T>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Slots {
+            values: values.into_iter().map(Some).collect(),
+        }
+    }
+
+    /// Choices for every value still in the slots, in original order.
+    pub(crate) fn choices(&self) -> Vec<IndexedChoice<'_, T>> {
+        self.values
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| slot.as_ref().map(|value| IndexedChoice { value, index }))
+            .collect()
+    }
+
+    pub(crate) fn choices_fixed<const N: usize>(&self) -> [IndexedChoice<'_, T>; N] {
+        match self.choices().try_into() {
+            Ok(choices) => choices,
+            Err(_) => unreachable!("slots were created from {} values", N),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub(crate) fn take(&mut self, index: usize) -> T {
+        // choices can't be duplicated, so each index is only ever taken once
+        self.values[index]
+            .take()
+            .expect("choice was already taken from its slot")
+    }
+
+    pub(crate) fn take_all<I>(&mut self, indices: I) -> Vec<T>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        indices.into_iter().map(|i| self.take(i)).collect()
+    }
+
+    /// The values that haven't been taken, in original order.
+    pub(crate) fn into_rest(self) -> Vec<T> {
+        self.values.into_iter().flatten().collect()
+    }
+
+    /// Like [into_rest](Slots::into_rest), but with the original index of each value.
+    pub(crate) fn into_rest_indexed(self) -> Vec<(usize, T)> {
+        self.values
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, slot)| slot.map(|value| (index, value)))
+            .collect()
+    }
 }
 
 pub(crate) fn to_values<T>(choices: Vec<Choice<'_, T>>) -> Vec<T> {
@@ -39,3 +547,150 @@ pub(crate) fn to_values<T>(choices: Vec<Choice<'_, T>>) -> Vec<T> {
     // may not need to reallocate
     choices.into_iter().map(Choice::into_inner).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory_tests::LARGE_N;
+    use crate::{select_from, select_from_fixed};
+
+    #[test]
+    fn choose_all_evens_with_filter() {
+        let chosen = select_from(1..=10).any_with(|choices| {
+            choices
+                .into_iter()
+                .filter_map(|c| c.filter(|n| n % 2 == 0))
+                .collect()
+        });
+
+        assert_eq!(chosen, [2, 4, 6, 8, 10]);
+    }
+
+    #[test]
+    fn choose_all_evens_with_retain() {
+        let chosen = select_from_fixed([1, 2, 3, 4])
+            .any_with(|choices| retain(choices.into_iter().collect(), |n| n % 2 == 0));
+
+        assert_eq!(chosen, [2, 4]);
+    }
+
+    #[test]
+    fn filter_rejects_failing_predicate() {
+        let chosen = select_from(vec![5]).any_with(|choices| {
+            choices
+                .into_iter()
+                .filter_map(|c| c.filter(|n| *n > 10))
+                .collect()
+        });
+
+        assert!(chosen.is_empty());
+    }
+
+    // a value that can't be cloned, so the helpers can only ever move choices around
+    #[derive(Debug, PartialEq, Eq)]
+    struct Heavy {
+        id: usize,
+        payload: [u8; 64],
+    }
+
+    #[test]
+    fn sort_helpers_on_many_choices() {
+        let pool = (0..LARGE_N).map(|id| Heavy {
+            id,
+            payload: [id as u8; 64],
+        });
+
+        let chosen = select_from(pool).any_with(|mut choices| {
+            // sort descending by id, then back into ascending by payload + id
+            sort_by(&mut choices, |a, b| b.id.cmp(&a.id));
+            assert_eq!(choices[0].id, LARGE_N - 1);
+
+            sort_by_key(&mut choices, |h| (h.payload[0], h.id));
+            assert_eq!(choices[0].id, 0);
+            assert_eq!(choices[1].id, if LARGE_N > 256 { 256 } else { 1 });
+
+            choices.truncate(2);
+            choices
+        });
+
+        assert_eq!(chosen[1].payload, [0; 64]);
+    }
+
+    #[test]
+    fn max_by_key_picks_last_maximum() {
+        let [chosen] = select_from([(1, 'a'), (3, 'b'), (3, 'c'), (2, 'd')])
+            .with(|choices| [max_by_key(choices, |(n, _)| *n).unwrap()]);
+
+        assert_eq!(chosen, (3, 'c'));
+    }
+
+    #[test]
+    fn max_by_key_of_nothing() {
+        let chosen = select_from(Vec::<i32>::new())
+            .any_with(|choices| max_by_key(choices, |n| *n).into_iter().collect());
+
+        assert!(chosen.is_empty());
+    }
+
+    #[test]
+    fn take_if_only_takes_once() {
+        let chosen = select_from([1, 2, 3]).any_with(|choices| {
+            let mut slots: Vec<_> = choices.into_iter().map(Some).collect();
+
+            let first = take_if(&mut slots[1], |_| true);
+            let second = take_if(&mut slots[1], |_| true);
+            assert!(first.is_some());
+            assert!(second.is_none());
+
+            first.into_iter().collect()
+        });
+
+        assert_eq!(chosen, [2]);
+    }
+
+    #[test]
+    fn take_if_false_leaves_slot() {
+        let chosen = select_from([1, 2, 3]).any_with(|choices| {
+            let mut slots: Vec<_> = choices.into_iter().map(Some).collect();
+
+            assert!(take_if(&mut slots[0], |n| *n > 1).is_none());
+            assert_eq!(slots[0].as_deref(), Some(&1));
+
+            slots.into_iter().flatten().collect()
+        });
+
+        assert_eq!(chosen, [1, 2, 3]);
+    }
+
+    // without the debug-only index, a choice is laid out exactly like its value
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn release_choices_are_transparent() {
+        use std::mem::{align_of, size_of};
+
+        assert_eq!(size_of::<Choice<'_, [u8; 3]>>(), 3);
+        assert_eq!(align_of::<Choice<'_, u64>>(), align_of::<u64>());
+        assert_eq!(size_of::<Choice<'_, ()>>(), 0);
+    }
+
+    #[test]
+    fn choice_sets_work_like_vecs() {
+        let [picked] = select_from((0..10).filter(|n| n % 3 == 0)).with_set(|mut choices| {
+            assert_eq!(choices.len(), 4);
+            assert!(!choices.is_empty());
+
+            sort_by_key(&mut choices, |n| std::cmp::Reverse(*n));
+            [choices.remove(1)]
+        });
+        assert_eq!(picked, 6);
+
+        let all = select_from(vec!['a', 'b']).any_with_set(|choices| choices.into_vec());
+        assert_eq!(all, ['a', 'b']);
+
+        let none = select_from(Vec::<u8>::new()).any_with_set(|choices| {
+            assert!(choices.is_empty());
+            choices.into()
+        });
+        assert!(none.is_empty());
+    }
+}
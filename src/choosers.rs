@@ -0,0 +1,192 @@
+//! Ready-made choosers for the common positional selections, for defaults and
+//! non-interactive fallbacks. Each function takes the choices as any iterable, so the same
+//! chooser works with the [Vec] of a [`Selector`](crate::Selector) and the array of a
+//! [`SelectorFixed`](crate::SelectorFixed):
+//! ```
+//! use choose_from::{choosers, select_from, select_from_fixed};
+//!
+//! let [a, b] = select_from(vec![1, 2, 3]).with(|c| choosers::first_k(c));
+//! assert_eq!([a, b], [1, 2]);
+//!
+//! let [c, d] = select_from_fixed([1, 2, 3]).with(|c| choosers::last_k(c));
+//! assert_eq!([c, d], [2, 3]);
+//! ```
+//! The choosers that choose any number of choices collect them into whatever collection the
+//! selection asks for:
+//! ```
+//! use choose_from::{choosers, select_from_fixed};
+//!
+//! let chosen = select_from_fixed([1, 2, 3, 4]).any_with(|c| choosers::every_nth(c, 2));
+//! assert_eq!(chosen, [1, 3]);
+//! ```
+//! The choosers panic with a message when the selection they describe is impossible, like
+//! taking the first 3 of 2 choices, so check [`Selector::len`](crate::Selector::len) first
+//! if the number of choices isn't known.
+
+use crate::Choice;
+use std::collections::HashSet;
+
+/// Chooses the first K choices, in order.
+///
+/// # Panics
+///
+/// Panics if there are fewer than K choices.
+/// ```
+/// use choose_from::{choosers, select_from};
+///
+/// let [first] = select_from(vec!["a", "b"]).with(|c| choosers::first_k(c));
+/// assert_eq!(first, "a");
+/// ```
+pub fn first_k<'guard, const K: usize, T>(
+    choices: impl IntoIterator<Item = Choice<'guard, T>>,
+) -> [Choice<'guard, T>; K] {
+    let mut choices = choices.into_iter();
+    let chosen: Vec<_> = choices.by_ref().take(K).collect();
+    let len = chosen.len() + choices.count();
+
+    match chosen.try_into() {
+        Ok(chosen) => chosen,
+        Err(_) => panic!("cannot choose the first {K} of {len} choices"),
+    }
+}
+
+/// Chooses the last K choices, in their original order.
+///
+/// # Panics
+///
+/// Panics if there are fewer than K choices.
+/// ```
+/// use choose_from::{choosers, select_from};
+///
+/// let last = select_from(1..=5).with(|c| choosers::last_k(c));
+/// assert_eq!(last, [4, 5]);
+/// ```
+pub fn last_k<'guard, const K: usize, T>(
+    choices: impl IntoIterator<Item = Choice<'guard, T>>,
+) -> [Choice<'guard, T>; K] {
+    let mut choices: Vec<_> = choices.into_iter().collect();
+    let len = choices.len();
+    let Some(start) = len.checked_sub(K) else {
+        panic!("cannot choose the last {K} of {len} choices");
+    };
+
+    match choices.split_off(start).try_into() {
+        Ok(chosen) => chosen,
+        Err(_) => unreachable!("exactly {} choices were split off", K),
+    }
+}
+
+/// Chooses every nth choice, starting with the first.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+/// ```
+/// use choose_from::{choosers, select_from};
+///
+/// let chosen = select_from(0..10).any_with(|c| choosers::every_nth(c, 3));
+/// assert_eq!(chosen, [0, 3, 6, 9]);
+/// ```
+pub fn every_nth<'guard, T, B>(choices: impl IntoIterator<Item = Choice<'guard, T>>, n: usize) -> B
+where
+    B: FromIterator<Choice<'guard, T>>,
+{
+    assert!(n > 0, "every_nth needs a step of at least 1");
+
+    choices.into_iter().step_by(n).collect()
+}
+
+/// Chooses the choices at `positions`, in the order the positions are given.
+///
+/// # Panics
+///
+/// Panics if a position is out of range, or if a position is given twice.
+/// ```
+/// use choose_from::{choosers, select_from};
+///
+/// let chosen = select_from(["a", "b", "c", "d"]).any_with(|c| choosers::at_positions(c, &[3, 0]));
+/// assert_eq!(chosen, ["d", "a"]);
+/// ```
+pub fn at_positions<'guard, T, B>(
+    choices: impl IntoIterator<Item = Choice<'guard, T>>,
+    positions: &[usize],
+) -> B
+where
+    B: FromIterator<Choice<'guard, T>>,
+{
+    let mut slots: Vec<_> = choices.into_iter().map(Some).collect();
+    let len = slots.len();
+    let mut seen = HashSet::with_capacity(positions.len());
+
+    for &position in positions {
+        assert!(
+            position < len,
+            "position {position} is out of range for {len} choices"
+        );
+        assert!(seen.insert(position), "position {position} was given twice");
+    }
+
+    positions
+        .iter()
+        .map(|&p| slots[p].take().expect("positions are distinct"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{select_from, select_from_fixed};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn choosers_work_for_both_selectors() {
+        assert_eq!(select_from(vec![1, 2, 3]).with(|c| last_k(c)), [2, 3]);
+        assert_eq!(select_from_fixed([1, 2, 3]).with(|c| last_k(c)), [2, 3]);
+        assert_eq!(select_from_fixed([1, 2, 3]).with(|c| first_k(c)), [1]);
+        assert_eq!(
+            select_from_fixed(['a', 'b', 'c']).any_with(|c| at_positions(c, &[1])),
+            ['b']
+        );
+        assert_eq!(select_from(0..0).with(|c| first_k::<0, i32>(c)), []);
+    }
+
+    #[test]
+    fn choosers_collect_into_any_collection() {
+        // the chooser gets to reorder what every_nth picked before handing it back
+        let chosen = select_from(0..6).any_with(|c| {
+            let mut picked: VecDeque<_> = every_nth(c, 2);
+            picked.rotate_left(1);
+            picked.into()
+        });
+        assert_eq!(chosen, [2, 4, 0]);
+
+        // choices can come from any iterator, like one that already skipped some
+        let chosen = select_from_fixed([1, 2, 3, 4])
+            .any_with(|c| at_positions(c.into_iter().rev(), &[0, 2]));
+        assert_eq!(chosen, [4, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot choose the first 3 of 2 choices")]
+    fn first_k_of_too_few() {
+        select_from(vec![1, 2]).with(|c| first_k::<3, _>(c));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot choose the last 2 of 1 choices")]
+    fn last_k_of_too_few() {
+        select_from(vec![1]).with(|c| last_k::<2, _>(c));
+    }
+
+    #[test]
+    #[should_panic(expected = "position 4 is out of range for 4 choices")]
+    fn out_of_range_position() {
+        select_from(0..4).any_with(|c| at_positions(c, &[0, 4]));
+    }
+
+    #[test]
+    #[should_panic(expected = "position 1 was given twice")]
+    fn repeated_position() {
+        select_from(0..4).any_with(|c| at_positions(c, &[1, 2, 1]));
+    }
+}
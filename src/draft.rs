@@ -0,0 +1,93 @@
+//! Selections over several rounds, where each round chooses from what the earlier rounds
+//! left behind. See [rounds](crate::selector::Selector::rounds).
+
+use crate::choice::IndexedChoice;
+use crate::Selector;
+use std::mem;
+
+/// A pool of choices that is chosen from over several rounds. Whatever is picked in a
+/// round is removed from the pool, so no value can be picked twice.
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct Draft<T> {
+    pool: Vec<T>,
+}
+
+impl<T> Draft<T> {
+    pub(crate) fn with_pool(pool: Vec<T>) -> Draft<T> {
+        Draft { pool }
+    }
+
+    /// Runs one round, where `chooser` is given the values still in the pool and returns a
+    /// K-selection of them, which is removed from the pool and returned. If the chooser
+    /// panics the pool is left empty.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let mut draft = select_from(vec!["elf", "orc", "dwarf"]).rounds();
+    ///
+    /// let [first] = draft.pick(|mut choices| [choices.remove(1)]);
+    /// let [second] = draft.pick(|mut choices| [choices.remove(1)]);
+    ///
+    /// assert_eq!([first, second], ["orc", "dwarf"]);
+    /// assert_eq!(draft.finish(), ["elf"]);
+    /// ```
+    pub fn pick<const K: usize, C>(&mut self, chooser: C) -> [T; K]
+    where
+        C: FnOnce(Vec<IndexedChoice<'_, T>>) -> [IndexedChoice<'_, T>; K],
+    {
+        let pool = mem::take(&mut self.pool);
+        let (chosen, rest) = Selector::with_choices(pool).with_rest(chooser);
+        self.pool = rest;
+
+        chosen
+    }
+
+    /// Like [pick](Draft::pick), but any number of values can be picked in the round.
+    pub fn pick_any<C>(&mut self, chooser: C) -> Vec<T>
+    where
+        C: FnOnce(Vec<IndexedChoice<'_, T>>) -> Vec<IndexedChoice<'_, T>>,
+    {
+        let pool = mem::take(&mut self.pool);
+        let (chosen, rest) = Selector::with_choices(pool).partition_with(chooser);
+        self.pool = rest;
+
+        chosen
+    }
+
+    /// The values that haven't been picked yet, in their original order.
+    pub fn remaining(&self) -> &[T] {
+        &self.pool
+    }
+
+    /// Ends the draft, giving back the values that were never picked.
+    pub fn finish(self) -> Vec<T> {
+        self.pool
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::select_from;
+
+    #[test]
+    fn three_rounds_never_repeat_a_value() {
+        let mut draft = select_from(0..10).rounds();
+        let mut picked = Vec::new();
+
+        for _ in 0..3 {
+            // always pick the first and last of what is left
+            let [first, last] = draft.pick(|mut choices| {
+                let last = choices.pop().unwrap();
+                [choices.remove(0), last]
+            });
+            picked.extend([first, last]);
+        }
+
+        assert_eq!(picked, [0, 9, 1, 8, 2, 7]);
+        assert_eq!(draft.remaining(), [3, 4, 5, 6]);
+
+        let odd = draft.pick_any(|choices| choices.into_iter().filter(|c| **c % 2 == 1).collect());
+        assert_eq!(odd, [3, 5]);
+        assert_eq!(draft.finish(), [4, 6]);
+    }
+}
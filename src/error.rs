@@ -0,0 +1,604 @@
+//! Errors returned by the selection methods that validate what a chooser returned.
+//!
+//! The fallible selections return a [ChooseError], which can be matched on through its
+//! [kind](ChooseError::kind), and which carries one of the payload types in this module.
+
+use std::error::Error;
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
+
+/// Returned when a chooser returns a different number of choices than was required.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct WrongSelectionCount {
+    /// The number of choices that were required
+    pub expected: usize,
+    /// The number of choices the chooser actually returned
+    pub actual: usize,
+}
+
+impl fmt::Display for WrongSelectionCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {} chosen values, but {} were chosen",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl Error for WrongSelectionCount {}
+
+/// Returned when a chooser returns a number of choices outside of the allowed range.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct SelectionCountError {
+    /// The lower bound of the allowed range
+    pub start: Bound<usize>,
+    /// The upper bound of the allowed range
+    pub end: Bound<usize>,
+    /// The number of choices the chooser actually returned
+    pub actual: usize,
+}
+
+impl SelectionCountError {
+    /// Checks `actual` against `bounds`, returning an error if it is out of range.
+    pub(crate) fn check<R>(bounds: &R, actual: usize) -> Result<(), SelectionCountError>
+    where
+        R: RangeBounds<usize>,
+    {
+        if bounds.contains(&actual) {
+            Ok(())
+        } else {
+            Err(SelectionCountError {
+                start: bounds.start_bound().cloned(),
+                end: bounds.end_bound().cloned(),
+                actual,
+            })
+        }
+    }
+}
+
+// so the error can be used to re-check a new selection with the same bounds
+impl RangeBounds<usize> for SelectionCountError {
+    fn start_bound(&self) -> Bound<&usize> {
+        self.start.as_ref()
+    }
+
+    fn end_bound(&self) -> Bound<&usize> {
+        self.end.as_ref()
+    }
+}
+
+impl fmt::Display for SelectionCountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected ")?;
+        match (self.start, self.end) {
+            (Bound::Unbounded, Bound::Unbounded) => write!(f, "any number of")?,
+            (start, Bound::Unbounded) => write!(f, "at least {}", inclusive_start(start))?,
+            (Bound::Unbounded, end) => write!(f, "at most {}", inclusive_end(end))?,
+            (start, end) => write!(
+                f,
+                "between {} and {}",
+                inclusive_start(start),
+                inclusive_end(end)
+            )?,
+        }
+        write!(f, " chosen values, but {} were chosen", self.actual)
+    }
+}
+
+fn inclusive_start(bound: Bound<usize>) -> usize {
+    match bound {
+        Bound::Included(n) => n,
+        Bound::Excluded(n) => n.saturating_add(1),
+        Bound::Unbounded => 0,
+    }
+}
+
+// only the display uses this, and an empty range like ..0 reads fine as "at most 0"
+fn inclusive_end(bound: Bound<usize>) -> usize {
+    match bound {
+        Bound::Included(n) => n,
+        Bound::Excluded(n) => n.saturating_sub(1),
+        Bound::Unbounded => usize::MAX,
+    }
+}
+
+impl Error for SelectionCountError {}
+
+/// Returned when a chosen pair doesn't satisfy the required relation.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct UnrelatedPair {
+    /// The position of the offending pair in the chooser's returned pairs
+    pub pair: usize,
+}
+
+impl fmt::Display for UnrelatedPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "chosen pair {} is not related", self.pair)
+    }
+}
+
+impl Error for UnrelatedPair {}
+
+/// Returned when the choice returned for a group belongs to a different group, see
+/// [select_one_per_group](crate::select_one_per_group).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct WrongGroup {
+    /// The position of the group that got a choice from another group
+    pub group: usize,
+}
+
+impl fmt::Display for WrongGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the choice for group {} is from another group",
+            self.group
+        )
+    }
+}
+
+impl Error for WrongGroup {}
+
+/// Returned when a selection tree is deeper than the configured maximum depth,
+/// which usually means the tree has a cycle.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct DepthExceeded {
+    /// The maximum depth that was configured
+    pub max_depth: usize,
+}
+
+impl fmt::Display for DepthExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "selection tree is deeper than the maximum depth of {}",
+            self.max_depth
+        )
+    }
+}
+
+impl Error for DepthExceeded {}
+
+/// Returned when two chosen values share a key that was required to be distinct, see
+/// [distinct_by_with](crate::Selector::distinct_by_with).
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct DuplicateKey {
+    /// The [Debug](fmt::Debug) form of the key shared by at least two of the chosen values
+    pub key: String,
+}
+
+impl fmt::Display for DuplicateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "more than one chosen value has the key {}", self.key)
+    }
+}
+
+impl Error for DuplicateKey {}
+
+/// Returned when a chooser rejected every attempt it was given, see
+/// [with_retries](crate::Selector::with_retries).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct RetriesExhausted<E> {
+    /// The number of attempts the chooser was given
+    pub attempts: usize,
+    /// Why the chooser rejected its last attempt
+    pub last_reason: E,
+}
+
+impl<E> fmt::Display for RetriesExhausted<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "chooser rejected all {} attempts", self.attempts)
+    }
+}
+
+impl<E: Error + 'static> Error for RetriesExhausted<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.last_reason)
+    }
+}
+
+/// Why a chooser declined to make a selection, see
+/// [with_declinable](crate::Selector::with_declinable). Unlike a bare `None`, the reason
+/// makes it back to the caller.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum Declined {
+    /// The user explicitly cancelled the selection
+    UserCancelled,
+    /// No selection was made in time
+    TimedOut,
+    /// None of the choices were acceptable
+    NoAcceptableOption {
+        /// Why none of the choices were acceptable
+        reason: String,
+    },
+    /// Any other reason, like the chooser's UI failing
+    Other(Box<dyn Error + Send + Sync>),
+}
+
+impl fmt::Display for Declined {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Declined::UserCancelled => write!(f, "the selection was cancelled"),
+            Declined::TimedOut => write!(f, "the selection timed out"),
+            Declined::NoAcceptableOption { reason } => {
+                write!(f, "none of the choices were acceptable: {reason}")
+            }
+            Declined::Other(_) => write!(f, "the selection was declined"),
+        }
+    }
+}
+
+impl Error for Declined {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Declined::Other(err) => Some(&**err),
+            _ => None,
+        }
+    }
+}
+
+/// Returned when a chooser chose two equal values for a set, see
+/// [set_with](crate::Selector::set_with).
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct DuplicateSelection {
+    /// The [Debug](fmt::Debug) form of the value that was chosen more than once
+    pub value: String,
+}
+
+impl fmt::Display for DuplicateSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the value {} was chosen more than once", self.value)
+    }
+}
+
+impl Error for DuplicateSelection {}
+
+/// Returned when a fixed selector is made from a number of values other than its size.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct WrongLength {
+    /// The size of the fixed selector
+    pub expected: usize,
+    /// The number of values it was made from
+    pub actual: usize,
+}
+
+impl fmt::Display for WrongLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {} choices, but {} were given",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl Error for WrongLength {}
+
+/// Returned when a selector that needs at least one choice is created from no choices,
+/// see [select_from_nonempty](crate::select_from_nonempty).
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct EmptyChoices;
+
+impl fmt::Display for EmptyChoices {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "there are no choices to choose from")
+    }
+}
+
+impl Error for EmptyChoices {}
+
+/// The error returned by fallible selections. Like [std::io::Error] it is opaque, use
+/// [kind](ChooseError::kind) to match on what went wrong and the `as_*` accessors to get
+/// the details.
+/// ```
+/// use choose_from::select_from;
+/// use choose_from::error::ChooseErrorKind;
+///
+/// let err = select_from(vec![1, 2, 3])
+///     .exact_with(2, |choices| choices)
+///     .unwrap_err();
+///
+/// match err.kind() {
+///     ChooseErrorKind::WrongCount => assert_eq!(err.as_wrong_count().unwrap().actual, 3),
+///     // new kinds may be added in any release
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct ChooseError {
+    repr: Repr,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+enum Repr {
+    WrongCount(WrongSelectionCount),
+    CountOutOfRange(SelectionCountError),
+    UnrelatedPair(UnrelatedPair),
+    DepthExceeded(DepthExceeded),
+    WrongGroup(WrongGroup),
+    DuplicateKey(DuplicateKey),
+    DuplicateSelection(DuplicateSelection),
+}
+
+/// What kind of [ChooseError] happened. More kinds may be added without a breaking release,
+/// so matches need a wildcard arm.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ChooseErrorKind {
+    /// A different number of choices was chosen than required, see [WrongSelectionCount]
+    WrongCount,
+    /// The number of chosen choices was outside the allowed range, see [SelectionCountError]
+    CountOutOfRange,
+    /// A chosen pair wasn't related, see [UnrelatedPair]
+    UnrelatedPair,
+    /// A selection tree was too deep, see [DepthExceeded]
+    DepthExceeded,
+    /// A group got a choice from another group, see [WrongGroup]
+    WrongGroup,
+    /// Two chosen values shared a key, see [DuplicateKey]
+    DuplicateKey,
+    /// Two chosen values were equal, see [DuplicateSelection]
+    DuplicateSelection,
+}
+
+impl ChooseError {
+    /// What kind of error this is.
+    pub fn kind(&self) -> ChooseErrorKind {
+        match self.repr {
+            Repr::WrongCount(_) => ChooseErrorKind::WrongCount,
+            Repr::CountOutOfRange(_) => ChooseErrorKind::CountOutOfRange,
+            Repr::UnrelatedPair(_) => ChooseErrorKind::UnrelatedPair,
+            Repr::DepthExceeded(_) => ChooseErrorKind::DepthExceeded,
+            Repr::WrongGroup(_) => ChooseErrorKind::WrongGroup,
+            Repr::DuplicateKey(_) => ChooseErrorKind::DuplicateKey,
+            Repr::DuplicateSelection(_) => ChooseErrorKind::DuplicateSelection,
+        }
+    }
+
+    /// The details of a [WrongCount](ChooseErrorKind::WrongCount) error.
+    pub fn as_wrong_count(&self) -> Option<&WrongSelectionCount> {
+        match &self.repr {
+            Repr::WrongCount(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// The details of a [CountOutOfRange](ChooseErrorKind::CountOutOfRange) error.
+    pub fn as_count_out_of_range(&self) -> Option<&SelectionCountError> {
+        match &self.repr {
+            Repr::CountOutOfRange(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// The details of an [UnrelatedPair](ChooseErrorKind::UnrelatedPair) error.
+    pub fn as_unrelated_pair(&self) -> Option<&UnrelatedPair> {
+        match &self.repr {
+            Repr::UnrelatedPair(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// The details of a [DepthExceeded](ChooseErrorKind::DepthExceeded) error.
+    pub fn as_depth_exceeded(&self) -> Option<&DepthExceeded> {
+        match &self.repr {
+            Repr::DepthExceeded(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// The details of a [WrongGroup](ChooseErrorKind::WrongGroup) error.
+    pub fn as_wrong_group(&self) -> Option<&WrongGroup> {
+        match &self.repr {
+            Repr::WrongGroup(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// The details of a [DuplicateKey](ChooseErrorKind::DuplicateKey) error.
+    pub fn as_duplicate_key(&self) -> Option<&DuplicateKey> {
+        match &self.repr {
+            Repr::DuplicateKey(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// The details of a [DuplicateSelection](ChooseErrorKind::DuplicateSelection) error.
+    pub fn as_duplicate_selection(&self) -> Option<&DuplicateSelection> {
+        match &self.repr {
+            Repr::DuplicateSelection(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<WrongSelectionCount> for ChooseError {
+    fn from(e: WrongSelectionCount) -> ChooseError {
+        ChooseError {
+            repr: Repr::WrongCount(e),
+        }
+    }
+}
+
+impl From<SelectionCountError> for ChooseError {
+    fn from(e: SelectionCountError) -> ChooseError {
+        ChooseError {
+            repr: Repr::CountOutOfRange(e),
+        }
+    }
+}
+
+impl From<UnrelatedPair> for ChooseError {
+    fn from(e: UnrelatedPair) -> ChooseError {
+        ChooseError {
+            repr: Repr::UnrelatedPair(e),
+        }
+    }
+}
+
+impl From<DepthExceeded> for ChooseError {
+    fn from(e: DepthExceeded) -> ChooseError {
+        ChooseError {
+            repr: Repr::DepthExceeded(e),
+        }
+    }
+}
+
+impl From<WrongGroup> for ChooseError {
+    fn from(e: WrongGroup) -> ChooseError {
+        ChooseError {
+            repr: Repr::WrongGroup(e),
+        }
+    }
+}
+
+impl From<DuplicateKey> for ChooseError {
+    fn from(e: DuplicateKey) -> ChooseError {
+        ChooseError {
+            repr: Repr::DuplicateKey(e),
+        }
+    }
+}
+
+impl From<DuplicateSelection> for ChooseError {
+    fn from(e: DuplicateSelection) -> ChooseError {
+        ChooseError {
+            repr: Repr::DuplicateSelection(e),
+        }
+    }
+}
+
+impl fmt::Display for ChooseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.repr {
+            Repr::WrongCount(e) => e.fmt(f),
+            Repr::CountOutOfRange(e) => e.fmt(f),
+            Repr::UnrelatedPair(e) => e.fmt(f),
+            Repr::DepthExceeded(e) => e.fmt(f),
+            Repr::WrongGroup(e) => e.fmt(f),
+            Repr::DuplicateKey(e) => e.fmt(f),
+            Repr::DuplicateSelection(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for ChooseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_error_display() {
+        let at_least = SelectionCountError::check(&(2..), 1).unwrap_err();
+        assert_eq!(
+            at_least.to_string(),
+            "expected at least 2 chosen values, but 1 were chosen"
+        );
+
+        let between = SelectionCountError::check(&(2..5), 7).unwrap_err();
+        assert_eq!(
+            between.to_string(),
+            "expected between 2 and 4 chosen values, but 7 were chosen"
+        );
+
+        let at_most = SelectionCountError::check(&(..=3), 4).unwrap_err();
+        assert_eq!(
+            at_most.to_string(),
+            "expected at most 3 chosen values, but 4 were chosen"
+        );
+    }
+
+    #[test]
+    fn choose_error_kinds_and_accessors() {
+        let wrong = ChooseError::from(WrongSelectionCount {
+            expected: 2,
+            actual: 3,
+        });
+        assert_eq!(wrong.kind(), ChooseErrorKind::WrongCount);
+        assert_eq!(wrong.as_wrong_count().unwrap().expected, 2);
+        assert!(wrong.as_count_out_of_range().is_none());
+        assert_eq!(
+            wrong.to_string(),
+            "expected 2 chosen values, but 3 were chosen"
+        );
+
+        let range = ChooseError::from(SelectionCountError::check(&(1..), 0).unwrap_err());
+        assert_eq!(range.kind(), ChooseErrorKind::CountOutOfRange);
+        assert_eq!(range.as_count_out_of_range().unwrap().actual, 0);
+        assert!(range.as_unrelated_pair().is_none());
+        assert_eq!(
+            range.to_string(),
+            "expected at least 1 chosen values, but 0 were chosen"
+        );
+
+        let pair = ChooseError::from(UnrelatedPair { pair: 4 });
+        assert_eq!(pair.kind(), ChooseErrorKind::UnrelatedPair);
+        assert_eq!(pair.as_unrelated_pair().unwrap().pair, 4);
+        assert!(pair.as_depth_exceeded().is_none());
+        assert_eq!(pair.to_string(), "chosen pair 4 is not related");
+
+        let depth = ChooseError::from(DepthExceeded { max_depth: 8 });
+        assert_eq!(depth.kind(), ChooseErrorKind::DepthExceeded);
+        assert_eq!(depth.as_depth_exceeded().unwrap().max_depth, 8);
+        assert!(depth.as_wrong_count().is_none());
+        assert_eq!(
+            depth.to_string(),
+            "selection tree is deeper than the maximum depth of 8"
+        );
+
+        let group = ChooseError::from(WrongGroup { group: 2 });
+        assert_eq!(group.kind(), ChooseErrorKind::WrongGroup);
+        assert_eq!(group.as_wrong_group().unwrap().group, 2);
+        assert!(group.as_unrelated_pair().is_none());
+        assert_eq!(
+            group.to_string(),
+            "the choice for group 2 is from another group"
+        );
+
+        let key = ChooseError::from(DuplicateKey {
+            key: String::from("3"),
+        });
+        assert_eq!(key.kind(), ChooseErrorKind::DuplicateKey);
+        assert_eq!(key.as_duplicate_key().unwrap().key, "3");
+        assert!(key.as_duplicate_selection().is_none());
+        assert_eq!(key.to_string(), "more than one chosen value has the key 3");
+
+        let equal = ChooseError::from(DuplicateSelection {
+            value: String::from("\"red\""),
+        });
+        assert_eq!(equal.kind(), ChooseErrorKind::DuplicateSelection);
+        assert_eq!(equal.as_duplicate_selection().unwrap().value, "\"red\"");
+        assert!(equal.as_duplicate_key().is_none());
+        assert_eq!(
+            equal.to_string(),
+            "the value \"red\" was chosen more than once"
+        );
+    }
+
+    #[test]
+    fn declined_display_and_source() {
+        assert_eq!(
+            Declined::UserCancelled.to_string(),
+            "the selection was cancelled"
+        );
+        assert_eq!(Declined::TimedOut.to_string(), "the selection timed out");
+
+        let none = Declined::NoAcceptableOption {
+            reason: String::from("all targets are immune"),
+        };
+        assert_eq!(
+            none.to_string(),
+            "none of the choices were acceptable: all targets are immune"
+        );
+        assert!(none.source().is_none());
+
+        let other = Declined::Other(EmptyChoices.into());
+        assert_eq!(
+            other.source().unwrap().to_string(),
+            "there are no choices to choose from"
+        );
+    }
+}
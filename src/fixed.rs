@@ -1,9 +1,16 @@
+use crate::choice::{IndexedChoice, Slots};
+use crate::error::{ChooseError, SelectionCountError, WrongLength, WrongSelectionCount};
+use crate::mapped::MappedSelectorFixed;
+use crate::postcondition::postcondition;
+use crate::swipe::Swipe;
+use crate::Selector;
 use crate::{choice, Choice, Guard};
+use std::ops::{Bound, RangeBounds};
 
 /// Wraps a fixed number of choices and provides methods that guarantee selection from those choices,
 /// where N is the possible number of choices set at compile time.
 // probably wouldn't need this type with HKTs :(
-#[derive(Debug, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub struct SelectorFixed<const N: usize, T> {
     choices: [T; N],
 }
@@ -30,6 +37,12 @@ impl<const N: usize, T> SelectorFixed<N, T> {
     ///
     /// assert_eq!(chosen, ["Hi", "are ya?"]);
     /// ```
+    /// Choosing more values than there are choices is a compile time error:
+    /// ```compile_fail
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let chosen: [i32; 3] = select_from_fixed([1, 2]).with(|_| unreachable!());
+    /// ```
     // we pass our possible choices to the function wrapped in Choice, which only allows
     // inspection of the value, and it must return an array of size K back full
     // of our choices. The values returned are GUARANTEED to only come from our original
@@ -38,6 +51,8 @@ impl<const N: usize, T> SelectorFixed<N, T> {
     where
         C: FnOnce([Choice<'_, T>; N]) -> [Choice<'_, T>; K],
     {
+        let () = Fits::<K, N>::OK;
+
         // here we use a guard to prevent the caller from "smuggling" a value out of the closure.
         // This ensures that Choice values built from our given choices are only
         // available within the closure (they can't escape), since Choice has no
@@ -45,7 +60,10 @@ impl<const N: usize, T> SelectorFixed<N, T> {
         let _guard = Guard;
         let choices = self.into_choices(&_guard);
 
-        chooser(choices).map(Choice::into_inner)
+        let chosen = chooser(choices);
+        postcondition!(unique_choices(&chosen, N));
+
+        chosen.map(Choice::into_inner)
         // _guard is dropped when function returns, which means that no one
         // has any Choice values anymore
     }
@@ -64,6 +82,17 @@ impl<const N: usize, T> SelectorFixed<N, T> {
     ///
     /// assert_eq!(chosen, ["Hi", "are ya?"]);
     /// ```
+    /// Just like [Selector::any_with](crate::Selector::any_with), the returned choices must come
+    /// from this call, so smuggling choices out of the chooser is rejected:
+    /// ```compile_fail
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let mut smuggler = Vec::new();
+    /// select_from_fixed([1, 2, 3]).any_with(|[one, two, three]| {
+    ///     smuggler.push(three);
+    ///     vec![one, two]
+    /// });
+    /// ```
     pub fn any_with<C>(self, chooser: C) -> Vec<T>
     where
         C: FnOnce([Choice<'_, T>; N]) -> Vec<Choice<'_, T>>,
@@ -71,10 +100,1220 @@ impl<const N: usize, T> SelectorFixed<N, T> {
         let _guard = Guard;
         let choices = self.into_choices(&_guard);
 
-        choice::to_values(chooser(choices))
+        let chosen = chooser(choices);
+        postcondition!(unique_choices(&chosen, N));
+
+        choice::to_values(chosen)
+    }
+
+    /// Like [with](SelectorFixed::with), but `ctx` is handed to the chooser along with the
+    /// choices, and handed back out once it returns, see
+    /// [Selector::with_context](crate::Selector::with_context).
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let mut log = Vec::new();
+    ///
+    /// let ([chosen], log) = select_from_fixed([3, 9, 4]).with_context(&mut log, |log, [a, b, c]| {
+    ///     log.push(format!("saw {} {} {}", *a, *b, *c));
+    ///     ([b], log)
+    /// });
+    ///
+    /// assert_eq!(chosen, 9);
+    /// assert_eq!(*log, ["saw 3 9 4"]);
+    /// ```
+    pub fn with_context<Ctx, const K: usize, C>(self, ctx: Ctx, chooser: C) -> ([T; K], Ctx)
+    where
+        C: FnOnce(Ctx, [Choice<'_, T>; N]) -> ([Choice<'_, T>; K], Ctx),
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+
+        let (chosen, ctx) = chooser(ctx, choices);
+        postcondition!(unique_choices(&chosen, N));
+
+        (chosen.map(Choice::into_inner), ctx)
+    }
+
+    /// Like [with](SelectorFixed::with), but each chosen value is returned together with its
+    /// index in the original array, no matter how the chooser rearranged them. The chooser
+    /// is handed [IndexedChoice]s, which know their index too.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let chosen = select_from_fixed(["ace", "king", "queen", "jack"]).with_indices(|mut choices| {
+    ///     choices.sort_by_key(|c| **c);
+    ///     let [ace, jack, ..] = choices;
+    ///     [jack, ace]
+    /// });
+    ///
+    /// assert_eq!(chosen, [(3, "jack"), (0, "ace")]);
+    /// ```
+    pub fn with_indices<const K: usize, C>(self, chooser: C) -> [(usize, T); K]
+    where
+        C: FnOnce([IndexedChoice<'_, T>; N]) -> [IndexedChoice<'_, T>; K],
+    {
+        let () = Fits::<K, N>::OK;
+
+        let mut slots = Slots::new(self.choices);
+        let chosen = chooser(slots.choices_fixed()).map(|c| c.index());
+        postcondition!(unique_indices(chosen, N));
+
+        chosen.map(|i| (i, slots.take(i)))
+    }
+
+    /// Like [with](SelectorFixed::with), but `chooser` returns a single choice, and its value
+    /// is returned as is instead of in an array.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let dessert = select_from_fixed(["cake", "pie"]).one_with(|[_, pie]| pie);
+    ///
+    /// assert_eq!(dessert, "pie");
+    /// ```
+    /// There is nothing to choose from zero choices, so that is a compile time error:
+    /// ```compile_fail
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let nothing: i32 = select_from_fixed([]).one_with(|[]| unreachable!());
+    /// ```
+    pub fn one_with<C>(self, chooser: C) -> T
+    where
+        C: FnOnce([Choice<'_, T>; N]) -> Choice<'_, T>,
+    {
+        // with checks at compile time that there is at least one choice
+        let [chosen] = self.with(|choices| [chooser(choices)]);
+        chosen
+    }
+
+    /// Like [with](SelectorFixed::with), but for choosers that can fail. An error from
+    /// `chooser` is returned as is, and none of the choices are returned with it.
+    /// ```
+    /// use std::io;
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let chosen = select_from_fixed(["yes", "no"]).try_with(|[yes, _]| {
+    ///     // imagine reading the answer from a prompt here
+    ///     let answer = "y";
+    ///     match answer {
+    ///         "y" => Ok([yes]),
+    ///         _ => Err(io::Error::new(io::ErrorKind::InvalidInput, "not an answer")),
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(chosen.unwrap(), ["yes"]);
+    /// ```
+    pub fn try_with<const K: usize, C, E>(self, chooser: C) -> Result<[T; K], E>
+    where
+        C: FnOnce([Choice<'_, T>; N]) -> Result<[Choice<'_, T>; K], E>,
+    {
+        let () = Fits::<K, N>::OK;
+
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+
+        let chosen = chooser(choices)?;
+        postcondition!(unique_choices(&chosen, N));
+
+        Ok(chosen.map(Choice::into_inner))
+    }
+
+    /// Like [any_with](SelectorFixed::any_with), but for choosers that can fail, see
+    /// [try_with](SelectorFixed::try_with).
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let chosen: Result<Vec<i32>, String> =
+    ///     select_from_fixed([1, 2, 3]).try_any_with(|_| Err(String::from("prompt closed")));
+    ///
+    /// assert_eq!(chosen.unwrap_err(), "prompt closed");
+    /// ```
+    pub fn try_any_with<C, E>(self, chooser: C) -> Result<Vec<T>, E>
+    where
+        C: FnOnce([Choice<'_, T>; N]) -> Result<Vec<Choice<'_, T>>, E>,
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+
+        let chosen = chooser(choices)?;
+        postcondition!(unique_choices(&chosen, N));
+
+        Ok(choice::to_values(chosen))
+    }
+
+    /// Like [with](SelectorFixed::with), but `chooser` may cancel the selection by returning
+    /// `None`, in which case all the choices are dropped.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// // imagine this is read from a prompt
+    /// let input = "q";
+    ///
+    /// let chosen = select_from_fixed(["rock", "paper", "scissors"]).opt_with(|[r, p, s]| {
+    ///     match input {
+    ///         "r" => Some([r]),
+    ///         "p" => Some([p]),
+    ///         "s" => Some([s]),
+    ///         _ => None,
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(chosen, None);
+    /// ```
+    pub fn opt_with<const K: usize, C>(self, chooser: C) -> Option<[T; K]>
+    where
+        C: FnOnce([Choice<'_, T>; N]) -> Option<[Choice<'_, T>; K]>,
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+
+        let chosen = chooser(choices)?;
+        postcondition!(unique_choices(&chosen, N));
+
+        Some(chosen.map(Choice::into_inner))
+    }
+
+    /// Like [opt_with](SelectorFixed::opt_with), but when `chooser` cancels, the choices are
+    /// handed back untouched and in their original order, instead of being dropped.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let hand = [String::from("ace"), String::from("king")];
+    ///
+    /// // the user pressed Esc
+    /// let chosen = select_from_fixed(hand).opt_with_recover::<1, _>(|_| None);
+    ///
+    /// let hand = chosen.unwrap_err();
+    /// assert_eq!(hand, ["ace", "king"]);
+    ///
+    /// let [card] = select_from_fixed(hand).opt_with_recover(|[_, king]| Some([king])).unwrap();
+    /// assert_eq!(card, "king");
+    /// ```
+    pub fn opt_with_recover<const K: usize, C>(self, chooser: C) -> Result<[T; K], [T; N]>
+    where
+        C: FnOnce([IndexedChoice<'_, T>; N]) -> Option<[IndexedChoice<'_, T>; K]>,
+    {
+        let () = Fits::<K, N>::OK;
+
+        let mut slots = Slots::new(self.choices);
+        let Some(chosen) = chooser(slots.choices_fixed()).map(|chosen| chosen.map(|c| c.index()))
+        else {
+            return match slots.into_rest().try_into() {
+                Ok(choices) => Err(choices),
+                Err(_) => unreachable!("nothing was taken out of the {} slots", N),
+            };
+        };
+        postcondition!(unique_indices(chosen, N));
+
+        Ok(chosen.map(|i| slots.take(i)))
+    }
+
+    /// Presents the choices one at a time, in their original order, and lets `decide` keep or
+    /// discard each one by handing it back in a [Swipe]. Unlike
+    /// [Selector::swipe_with](crate::Selector::swipe_with) there is no limit on how many are
+    /// kept. The kept values are returned in their original order.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    /// use choose_from::swipe::Swipe;
+    ///
+    /// // imagine each answer comes from an accept/skip button
+    /// let mut answers = [true, false, true].into_iter();
+    ///
+    /// let kept = select_from_fixed(["sword", "rope", "lamp"]).each_with(|choice| {
+    ///     if answers.next().unwrap() {
+    ///         Swipe::Keep(choice)
+    ///     } else {
+    ///         Swipe::Discard(choice)
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(kept, ["sword", "lamp"]);
+    /// ```
+    pub fn each_with<F>(self, mut decide: F) -> Vec<T>
+    where
+        F: FnMut(Choice<'_, T>) -> Swipe<'_, T>,
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+
+        let kept: Vec<_> = choices
+            .into_iter()
+            .filter_map(|choice| match decide(choice) {
+                Swipe::Keep(choice) => Some(choice),
+                Swipe::Discard(_) => None,
+            })
+            .collect();
+        postcondition!(unique_choices(&kept, N));
+
+        choice::to_values(kept)
+    }
+
+    /// Like [any_with](SelectorFixed::any_with), but the chosen values are collected into any
+    /// [FromIterator] collection instead of a [Vec].
+    /// ```
+    /// use std::collections::HashSet;
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let chosen: HashSet<_> = select_from_fixed(["Hi", "how", "are ya?"]).collect_with(|choices| {
+    ///     choices.into_iter().filter(|c| c.len() > 2).collect()
+    /// });
+    ///
+    /// assert!(chosen.contains("how"));
+    /// assert_eq!(chosen.len(), 2);
+    /// ```
+    // the chooser still returns a Vec: the returned collection would have to be generic over the
+    // guard lifetime, which a single type parameter can't express for closures
+    pub fn collect_with<B, C>(self, chooser: C) -> B
+    where
+        B: FromIterator<T>,
+        C: FnOnce([Choice<'_, T>; N]) -> Vec<Choice<'_, T>>,
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+
+        let chosen = chooser(choices);
+        postcondition!(unique_choices(&chosen, N));
+
+        chosen.into_iter().map(Choice::into_inner).collect()
+    }
+
+    /// Like [any_with](SelectorFixed::any_with), but the chosen values are returned in the order the
+    /// choices were presented, no matter what order the chooser returned them in.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let chosen = select_from_fixed(["ham", "olive", "onion", "pepper", "basil"]).ordered_with(|choices| {
+    ///     // the user ticked the boxes from the bottom up
+    ///     choices.into_iter().rev().step_by(2).collect()
+    /// });
+    ///
+    /// assert_eq!(chosen, ["ham", "onion", "basil"]);
+    /// ```
+    pub fn ordered_with<C>(self, chooser: C) -> Vec<T>
+    where
+        C: FnOnce([IndexedChoice<'_, T>; N]) -> Vec<IndexedChoice<'_, T>>,
+    {
+        let mut slots = Slots::new(self.choices);
+        let mut chosen: Vec<usize> = chooser(slots.choices_fixed())
+            .iter()
+            .map(IndexedChoice::index)
+            .collect();
+        postcondition!(unique_indices(chosen.iter().copied(), N));
+
+        chosen.sort_unstable();
+        slots.take_all(chosen)
+    }
+
+    /// Has `chooser` rank every choice, see [Selector::ranked_with](crate::Selector::ranked_with).
+    /// All N choices have to be returned, and a choice can't be returned twice, so the result
+    /// is always a permutation of the choices. Here the array types check that at compile
+    /// time, so unlike ranking with a [Selector], no error can happen.
+    /// [any_with](SelectorFixed::any_with) can return the choices in any order too, but it
+    /// can't tell whether some were left out.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let candidates = ["ann", "bob", "cid", "dee", "eve"];
+    ///
+    /// let ballot = select_from_fixed(candidates).ranked_with(|mut choices| {
+    ///     choices.sort_by_key(|c| std::cmp::Reverse(**c));
+    ///     choices
+    /// });
+    ///
+    /// assert_eq!(ballot, ["eve", "dee", "cid", "bob", "ann"]);
+    /// ```
+    /// Leaving a candidate out doesn't compile:
+    /// ```compile_fail
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let ballot = select_from_fixed(["ann", "bob", "cid"]).ranked_with(|[a, b, _]| [b, a]);
+    /// ```
+    pub fn ranked_with<C>(self, chooser: C) -> [T; N]
+    where
+        C: FnOnce([Choice<'_, T>; N]) -> [Choice<'_, T>; N],
+    {
+        self.with(chooser)
+    }
+
+    // the chosen values, and the rest in their original order, for both versions of with_rest
+    fn with_rest_vec<const K: usize, C>(self, chooser: C) -> ([T; K], Vec<T>)
+    where
+        C: FnOnce([IndexedChoice<'_, T>; N]) -> [IndexedChoice<'_, T>; K],
+    {
+        let () = Fits::<K, N>::OK;
+
+        let mut slots = Slots::new(self.choices);
+        let chosen = chooser(slots.choices_fixed()).map(|c| c.index());
+        postcondition!(unique_indices(chosen, N));
+
+        let chosen = chosen.map(|i| slots.take(i));
+        let rest = slots.into_rest();
+        postcondition!(partition(K, rest.len(), N));
+
+        (chosen, rest)
+    }
+
+    /// Like [any_with](SelectorFixed::any_with), but the values that weren't chosen are returned too,
+    /// in their original order.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let (hand, deck) = select_from_fixed(["ace", "king", "queen", "jack"]).partition_with(|choices| {
+    ///     choices.into_iter().filter(|c| c.starts_with('q') || c.starts_with('k')).collect()
+    /// });
+    ///
+    /// assert_eq!(hand, ["king", "queen"]);
+    /// assert_eq!(deck, ["ace", "jack"]);
+    /// ```
+    pub fn partition_with<C>(self, chooser: C) -> (Vec<T>, Vec<T>)
+    where
+        C: FnOnce([IndexedChoice<'_, T>; N]) -> Vec<IndexedChoice<'_, T>>,
+    {
+        let mut slots = Slots::new(self.choices);
+        let chosen: Vec<usize> = chooser(slots.choices_fixed())
+            .iter()
+            .map(IndexedChoice::index)
+            .collect();
+        postcondition!(unique_indices(chosen.iter().copied(), N));
+
+        let (chosen, rest) = (slots.take_all(chosen), slots.into_rest());
+        postcondition!(partition(chosen.len(), rest.len(), N));
+
+        (chosen, rest)
+    }
+
+    /// Like [any_with](SelectorFixed::any_with), but instead of the chosen values, a mask of
+    /// which positions were chosen is returned, along with every value in its original order.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let (mask, units) = select_from_fixed(["archer", "knight", "mage"]).mask_with(|[a, _, m]| vec![m, a]);
+    ///
+    /// assert_eq!(mask, [true, false, true]);
+    /// assert_eq!(units, ["archer", "knight", "mage"]);
+    /// ```
+    pub fn mask_with<C>(self, chooser: C) -> ([bool; N], [T; N])
+    where
+        C: FnOnce([IndexedChoice<'_, T>; N]) -> Vec<IndexedChoice<'_, T>>,
+    {
+        let slots = Slots::new(self.choices);
+        let chosen: Vec<usize> = chooser(slots.choices_fixed())
+            .iter()
+            .map(IndexedChoice::index)
+            .collect();
+        postcondition!(unique_indices(chosen.iter().copied(), N));
+
+        let mut mask = [false; N];
+        for i in chosen {
+            mask[i] = true;
+        }
+
+        match slots.into_rest().try_into() {
+            Ok(values) => (mask, values),
+            Err(_) => unreachable!("nothing was taken out of the {} slots", N),
+        }
+    }
+
+    /// Like [mask_with](SelectorFixed::mask_with), but the mask is packed into a [u64], with
+    /// the first choice as the lowest bit. More than 64 choices don't fit, which is a compile
+    /// time error.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let (mask, _) = select_from_fixed([10, 20, 30, 40]).bitmask_with(|[_, b, _, d]| vec![b, d]);
+    ///
+    /// assert_eq!(mask, 0b1010);
+    /// ```
+    /// ```compile_fail
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let (mask, _) = select_from_fixed([0; 65]).bitmask_with(|c| c.into());
+    /// ```
+    pub fn bitmask_with<C>(self, chooser: C) -> (u64, [T; N])
+    where
+        C: FnOnce([IndexedChoice<'_, T>; N]) -> Vec<IndexedChoice<'_, T>>,
+    {
+        let () = FitsBitmask::<N>::OK;
+
+        let (mask, values) = self.mask_with(chooser);
+        let bits = (0..N).filter(|&i| mask[i]).fold(0, |bits, i| bits | 1 << i);
+
+        (bits, values)
+    }
+
+    /// Like [any_with](SelectorFixed::any_with), but the number of chosen values must be exactly `k`.
+    /// This is useful when the number of values to choose is only known at runtime.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    /// use choose_from::error::WrongSelectionCount;
+    ///
+    /// // imagine this comes from the game state
+    /// let n = 2;
+    ///
+    /// let chosen = select_from_fixed([1, 2, 3, 4]).exact_with(n, |choices| {
+    ///     choices.into_iter().take(n).collect()
+    /// });
+    /// assert_eq!(chosen, Ok(vec![1, 2]));
+    ///
+    /// let wrong = select_from_fixed([1, 2, 3, 4]).exact_with(n, |choices| {
+    ///     choices.into_iter().take(3).collect()
+    /// });
+    /// assert_eq!(wrong.unwrap_err().as_wrong_count(), Some(&WrongSelectionCount { expected: 2, actual: 3 }));
+    /// ```
+    pub fn exact_with<C>(self, k: usize, chooser: C) -> Result<Vec<T>, ChooseError>
+    where
+        C: FnOnce([Choice<'_, T>; N]) -> Vec<Choice<'_, T>>,
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+
+        let chosen = chooser(choices);
+        postcondition!(unique_choices(&chosen, N));
+
+        if chosen.len() != k {
+            return Err(WrongSelectionCount {
+                expected: k,
+                actual: chosen.len(),
+            }
+            .into());
+        }
+
+        Ok(choice::to_values(chosen))
+    }
+
+    /// Like [any_with](SelectorFixed::any_with), but the number of chosen values must be within
+    /// `bounds`. On failure the error carries the bounds and how many values were chosen,
+    /// so a caller can ask again.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// // pick 2 to 4 toppings
+    /// let chosen = select_from_fixed(["ham", "olive", "onion", "pepper", "basil"]).bounded_with(2..=4, |choices| {
+    ///     choices.into_iter().take(3).collect()
+    /// });
+    /// assert_eq!(chosen, Ok(vec!["ham", "olive", "onion"]));
+    ///
+    /// let wrong = select_from_fixed(["ham", "olive", "onion", "pepper", "basil"]).bounded_with(2..=4, |choices| {
+    ///     choices.into_iter().take(1).collect()
+    /// });
+    /// assert_eq!(wrong.unwrap_err().as_count_out_of_range().unwrap().actual, 1);
+    /// ```
+    /// If no number of chosen values from 0 to N is within `bounds`, no selection could ever
+    /// satisfy them, so the error is returned without calling `chooser`, with `actual` set
+    /// to N, the most values there are to choose.
+    pub fn bounded_with<R, C>(self, bounds: R, chooser: C) -> Result<Vec<T>, ChooseError>
+    where
+        R: RangeBounds<usize>,
+        C: FnOnce([Choice<'_, T>; N]) -> Vec<Choice<'_, T>>,
+    {
+        let fewest = match bounds.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        if fewest > N || !bounds.contains(&fewest) {
+            return Err(SelectionCountError {
+                start: bounds.start_bound().cloned(),
+                end: bounds.end_bound().cloned(),
+                actual: N,
+            }
+            .into());
+        }
+
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+
+        let chosen = chooser(choices);
+        postcondition!(unique_choices(&chosen, N));
+
+        SelectionCountError::check(&bounds, chosen.len())?;
+
+        Ok(choice::to_values(chosen))
+    }
+
+    /// Turns every choice into a new value with `f` before a selection is made, keeping the
+    /// array form. The mapped values are what is chosen from and returned.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let chosen = select_from_fixed([1, 2, 3, 4])
+    ///     .map(|n| format!("#{n}"))
+    ///     .with(|[_, two, _, four]| [four, two]);
+    ///
+    /// assert_eq!(chosen, ["#4", "#2"]);
+    /// ```
+    pub fn map<U, F>(self, f: F) -> SelectorFixed<N, U>
+    where
+        F: FnMut(T) -> U,
+    {
+        SelectorFixed::with_choices(self.choices.map(f))
+    }
+
+    /// Shows the chooser a view of each value made by `f`, instead of the values themselves,
+    /// see [Selector::map_choices](crate::Selector::map_choices). The chooser picks from the
+    /// views, and the original values behind the chosen views are returned.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// // (row id, display text)
+    /// let rows = [(17, "apples"), (4, "pears"), (9, "plums"), (30, "figs")];
+    ///
+    /// let [row] = select_from_fixed(rows)
+    ///     .map_view(|row| row.1.to_uppercase())
+    ///     .with(|[_, _, plums, _]| [plums]);
+    ///
+    /// assert_eq!(row, (9, "plums"));
+    /// ```
+    pub fn map_view<U, F>(self, f: F) -> MappedSelectorFixed<N, T, U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        let views = self.choices.each_ref().map(f);
+        MappedSelectorFixed::with_views(self.choices, views)
     }
 
-    fn into_choices(self, _guard: &'_ Guard) -> [Choice<'_, T>; N] {
-        self.choices.map(|t| Choice::with_guard(t, _guard))
+    /// Gives back the choices without making a selection, so a selector can be built before
+    /// knowing whether it will be needed.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let selector = select_from_fixed([1, 2, 3]);
+    /// assert_eq!(selector.into_inner(), [1, 2, 3]);
+    /// ```
+    pub fn into_inner(self) -> [T; N] {
+        self.choices
+    }
+
+    /// The choices as a slice, for looking at them without making a selection.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let selector = select_from_fixed(["a", "b"]);
+    /// assert_eq!(selector.as_slice(), ["a", "b"]);
+    /// ```
+    pub fn as_slice(&self) -> &[T] {
+        &self.choices
+    }
+
+    /// An iterator over the choices, for looking at them without making a selection.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.choices.iter()
+    }
+
+    pub(crate) fn into_choices(self, _guard: &'_ Guard) -> [Choice<'_, T>; N] {
+        let mut index = 0;
+        self.choices.map(|t| {
+            let choice = Choice::with_guard(t, index, _guard);
+            index += 1;
+            choice
+        })
+    }
+}
+
+impl<const N: usize, T> From<[T; N]> for SelectorFixed<N, T> {
+    fn from(choices: [T; N]) -> Self {
+        SelectorFixed::with_choices(choices)
+    }
+}
+
+/// Fails with the expected and actual lengths if the [Vec] doesn't hold exactly N values.
+/// ```
+/// use choose_from::fixed::SelectorFixed;
+/// use choose_from::error::WrongLength;
+///
+/// let seats = SelectorFixed::<3, _>::try_from(vec!["a", "b", "c"]).unwrap();
+/// assert_eq!(seats.with(|[a, _, c]| [c, a]), ["c", "a"]);
+///
+/// let short = SelectorFixed::<3, _>::try_from(vec!["a"]);
+/// assert_eq!(short.unwrap_err(), WrongLength { expected: 3, actual: 1 });
+/// ```
+impl<const N: usize, T> TryFrom<Vec<T>> for SelectorFixed<N, T> {
+    type Error = WrongLength;
+
+    fn try_from(choices: Vec<T>) -> Result<Self, Self::Error> {
+        let actual = choices.len();
+
+        match choices.try_into() {
+            Ok(choices) => Ok(SelectorFixed::with_choices(choices)),
+            Err(_) => Err(WrongLength {
+                expected: N,
+                actual,
+            }),
+        }
+    }
+}
+
+/// Like the [Vec] conversion, but clones the values out of a slice.
+impl<const N: usize, T: Clone> TryFrom<&[T]> for SelectorFixed<N, T> {
+    type Error = WrongLength;
+
+    fn try_from(choices: &[T]) -> Result<Self, Self::Error> {
+        match <&[T; N]>::try_from(choices) {
+            Ok(choices) => Ok(SelectorFixed::with_choices(choices.clone())),
+            Err(_) => Err(WrongLength {
+                expected: N,
+                actual: choices.len(),
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<const N: usize, T> SelectorFixed<N, T> {
+    /// Like [concat](SelectorFixed::concat), but the resulting size is known at compile
+    /// time, so the joined selector stays fixed.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let both = select_from_fixed([1, 2]).concat_fixed(select_from_fixed([3]));
+    /// let chosen = both.with(|[one, _, three]| [one, three]);
+    ///
+    /// assert_eq!(chosen, [1, 3]);
+    /// ```
+    pub fn concat_fixed<const M: usize>(
+        self,
+        other: SelectorFixed<M, T>,
+    ) -> SelectorFixed<{ N + M }, T> {
+        let mut values = self.choices.into_iter().chain(other.choices);
+        SelectorFixed::with_choices(std::array::from_fn(|_| values.next().unwrap()))
+    }
+
+    /// Like [with_rest](SelectorFixed::with_rest), but the N - K values that weren't chosen
+    /// are returned as an array, since both sizes are known at compile time.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let (kept, discarded) = select_from_fixed(["ace", "two", "king", "two"])
+    ///     .with_rest_fixed(|[ace, two, king, _]| [king, two]);
+    ///
+    /// assert_eq!(kept, ["king", "two"]);
+    /// assert_eq!(discarded, ["ace", "two"]);
+    /// ```
+    pub fn with_rest_fixed<const K: usize, C>(self, chooser: C) -> ([T; K], [T; N - K])
+    where
+        C: FnOnce([IndexedChoice<'_, T>; N]) -> [IndexedChoice<'_, T>; K],
+    {
+        let (chosen, rest) = self.with_rest_vec(chooser);
+
+        match rest.try_into() {
+            Ok(rest) => (chosen, rest),
+            Err(_) => unreachable!("{} of {} values were chosen", K, N),
+        }
+    }
+
+    /// Like [reject_with](SelectorFixed::reject_with), but the N - K values that are kept
+    /// are returned as an array.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let maps = ["dust", "nuke", "mirage", "inferno", "vertigo"];
+    ///
+    /// let pool = select_from_fixed(maps).reject_with_fixed(|[dust, _, _, _, vertigo]| [vertigo, dust]);
+    ///
+    /// assert_eq!(pool, ["nuke", "mirage", "inferno"]);
+    /// ```
+    pub fn reject_with_fixed<const K: usize, C>(self, chooser: C) -> [T; N - K]
+    where
+        C: FnOnce([IndexedChoice<'_, T>; N]) -> [IndexedChoice<'_, T>; K],
+    {
+        self.with_rest_fixed(chooser).1
+    }
+
+    /// Like [split](SelectorFixed::split), but the resulting sizes are known at compile
+    /// time, so both halves stay fixed.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let (first, rest) = select_from_fixed([1, 2, 3]).split_fixed::<1>();
+    ///
+    /// assert_eq!(first.with(|[one]| [one]), [1]);
+    /// assert_eq!(rest.with(|[_, three]| [three]), [3]);
+    /// ```
+    pub fn split_fixed<const A: usize>(self) -> (SelectorFixed<A, T>, SelectorFixed<{ N - A }, T>) {
+        let mut values = self.choices.into_iter();
+        let first = std::array::from_fn(|_| values.next().unwrap());
+        let rest = std::array::from_fn(|_| values.next().unwrap());
+
+        (
+            SelectorFixed::with_choices(first),
+            SelectorFixed::with_choices(rest),
+        )
+    }
+}
+
+// Without const generic arithmetic we can't name the resulting sizes, so fall back to
+// dynamic selectors (the `_fixed` variants on nightly keep everything fixed)
+impl<const N: usize, T> SelectorFixed<N, T> {
+    /// Joins two fixed selectors into one, with the choices of `self` presented first.
+    ///
+    /// This returns a dynamic [Selector], since the size `N + M` can't be expressed on
+    /// stable. With the `nightly` feature, `concat_fixed` returns a
+    /// `SelectorFixed<{ N + M }, T>` instead.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let both = select_from_fixed([1, 2]).concat(select_from_fixed([3]));
+    /// let chosen = both.any_with(|choices| choices.into_iter().step_by(2).collect());
+    ///
+    /// assert_eq!(chosen, [1, 3]);
+    /// ```
+    pub fn concat<const M: usize>(self, other: SelectorFixed<M, T>) -> Selector<Vec<T>, T> {
+        let values = self.choices.into_iter().chain(other.choices).collect();
+        Selector::with_choices(values)
+    }
+
+    /// Like [with](SelectorFixed::with), but the values that weren't chosen are returned
+    /// too, in their original order.
+    ///
+    /// The rest is returned as a [Vec], since the size `N - K` can't be expressed on
+    /// stable. With the `nightly` feature, `with_rest_fixed` returns an array instead.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let (kept, discarded) = select_from_fixed(["ace", "two", "king", "two"])
+    ///     .with_rest(|[ace, two, king, _]| [king, two]);
+    ///
+    /// assert_eq!(kept, ["king", "two"]);
+    /// assert_eq!(discarded, ["ace", "two"]);
+    /// ```
+    pub fn with_rest<const K: usize, C>(self, chooser: C) -> ([T; K], Vec<T>)
+    where
+        C: FnOnce([IndexedChoice<'_, T>; N]) -> [IndexedChoice<'_, T>; K],
+    {
+        self.with_rest_vec(chooser)
+    }
+
+    /// The inverse of [with](SelectorFixed::with): `chooser` returns the K choices to
+    /// remove, and every other value is returned, in its original order. The removed values
+    /// are dropped.
+    ///
+    /// The values are returned as a [Vec], since the size `N - K` can't be expressed on
+    /// stable. With the `nightly` feature, `reject_with_fixed` returns an array instead.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let maps = ["dust", "nuke", "mirage", "inferno", "vertigo"];
+    ///
+    /// let pool = select_from_fixed(maps).reject_with(|[dust, _, _, _, vertigo]| [vertigo, dust]);
+    ///
+    /// assert_eq!(pool, ["nuke", "mirage", "inferno"]);
+    /// ```
+    pub fn reject_with<const K: usize, C>(self, chooser: C) -> Vec<T>
+    where
+        C: FnOnce([IndexedChoice<'_, T>; N]) -> [IndexedChoice<'_, T>; K],
+    {
+        self.with_rest_vec(chooser).1
+    }
+
+    /// Splits this selector into one holding the first `A` choices and one holding
+    /// the rest. `A` must not be larger than `N`.
+    ///
+    /// This returns dynamic [Selector]s, since the size `N - A` can't be expressed on
+    /// stable. With the `nightly` feature, `split_fixed` returns fixed selectors instead.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let (first, rest) = select_from_fixed([1, 2, 3]).split::<1>();
+    ///
+    /// assert_eq!(first.any_with(|choices| choices), [1]);
+    /// assert_eq!(rest.any_with(|choices| choices), [2, 3]);
+    /// ```
+    pub fn split<const A: usize>(self) -> (Selector<Vec<T>, T>, Selector<Vec<T>, T>) {
+        let () = Fits::<A, N>::OK;
+
+        let mut first: Vec<T> = self.choices.into();
+        let rest = first.split_off(A);
+
+        (Selector::with_choices(first), Selector::with_choices(rest))
+    }
+}
+
+// checks at compile time that K choices can be taken out of N, as an associated const
+// since inline const blocks count as generic constants under `generic_const_exprs`
+pub(crate) struct Fits<const K: usize, const N: usize>;
+
+impl<const K: usize, const N: usize> Fits<K, N> {
+    pub(crate) const OK: () = assert!(K <= N, "cannot choose more choices than there are");
+}
+
+// like Fits, but for masks packed into a u64
+struct FitsBitmask<const N: usize>;
+
+impl<const N: usize> FitsBitmask<N> {
+    const OK: () = assert!(N <= 64, "a u64 mask can't hold more than 64 choices");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{WrongLength, WrongSelectionCount};
+    use crate::fixed::SelectorFixed;
+    use crate::select_from_fixed;
+    use crate::swipe::Swipe;
+    use std::ops::Bound;
+    use std::rc::Rc;
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn concat_then_split_keeps_sizes() {
+        let both = select_from_fixed(["a", "b"]).concat_fixed(select_from_fixed(["c", "d", "e"]));
+        let (left, right) = both.split_fixed::<3>();
+
+        assert_eq!(left.with(|[a, b, c]| [c, b, a]), ["c", "b", "a"]);
+        assert_eq!(right.with(|[d, e]| [e, d]), ["e", "d"]);
+    }
+
+    #[cfg(feature = "nightly")]
+    #[test]
+    fn split_then_concat_round_trips() {
+        let hand = select_from_fixed([1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let (low, high) = hand.split_fixed::<4>();
+        let rejoined = low.concat_fixed(high);
+
+        assert_eq!(rejoined, select_from_fixed([1, 2, 3, 4, 5, 6, 7, 8]));
+    }
+
+    #[test]
+    fn concat_then_split_falls_back_to_dynamic() {
+        let both = select_from_fixed(["a", "b"]).concat(select_from_fixed(["c", "d", "e"]));
+        let chosen = both.any_with(|choices| choices);
+
+        assert_eq!(chosen, ["a", "b", "c", "d", "e"]);
+
+        let (left, right) = select_from_fixed(["a", "b", "c"]).split::<3>();
+
+        assert_eq!(left.any_with(|choices| choices), ["a", "b", "c"]);
+        assert!(right.any_with(|choices| choices).is_empty());
+    }
+
+    #[test]
+    fn rankings_are_permutations() {
+        let ranked =
+            select_from_fixed([3, 1, 2]).ranked_with(|[three, one, two]| [one, two, three]);
+        assert_eq!(ranked, [1, 2, 3]);
+
+        let empty: [u8; 0] = select_from_fixed([]).ranked_with(|[]| []);
+        assert_eq!(empty, []);
+    }
+
+    #[test]
+    fn indices_survive_swaps() {
+        let chosen = select_from_fixed(['a', 'b', 'c', 'd']).with_indices(|mut choices| {
+            choices.swap(0, 3);
+            choices.swap(1, 2);
+            let [first, second, ..] = choices;
+            [first, second]
+        });
+
+        assert_eq!(chosen, [(3, 'd'), (2, 'c')]);
+    }
+
+    #[test]
+    fn rejects_leave_the_rest_in_order() {
+        let pool = select_from_fixed([5, 3, 5, 1, 5]).reject_with(|[_, b, c, _, _]| [c, b]);
+        assert_eq!(pool[..], [5, 1, 5]);
+
+        // the rejected instances are dropped, the surviving ones are handed back
+        let first = Rc::new(1);
+        let pool = select_from_fixed([Rc::clone(&first), Rc::new(1)]).reject_with(|[_, b]| [b]);
+        assert!(Rc::ptr_eq(&pool[0], &first));
+
+        let none = select_from_fixed([1, 2]).reject_with(|[a, b]| [a, b]);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn masks_follow_the_chosen_positions() {
+        let (mask, values) = select_from_fixed([1, 2, 3]).mask_with(|_| Vec::new());
+        assert_eq!((mask, values), ([false; 3], [1, 2, 3]));
+
+        let (mask, values) = select_from_fixed([1, 2, 3]).mask_with(|c| c.into());
+        assert_eq!((mask, values), ([true; 3], [1, 2, 3]));
+
+        let (mask, _) = select_from_fixed([0; 6]).mask_with(|[a, _, _, d, _, f]| vec![f, a, d]);
+        assert_eq!(mask, [true, false, false, true, false, true]);
+
+        let (bits, _) = select_from_fixed([0; 6]).bitmask_with(|[a, _, _, d, _, f]| vec![f, a, d]);
+        assert_eq!(bits, 0b101001);
+
+        let (bits, _) = select_from_fixed([0u8; 64]).bitmask_with(|c| c.into());
+        assert_eq!(bits, u64::MAX);
+    }
+
+    #[test]
+    fn each_with_keeps_in_order() {
+        let mut asked = 0;
+        let all = select_from_fixed([1, 2, 3]).each_with(|choice| {
+            asked += 1;
+            Swipe::Keep(choice)
+        });
+        assert_eq!(all, [1, 2, 3]);
+
+        let none = select_from_fixed([1, 2, 3]).each_with(|choice| {
+            asked += 1;
+            Swipe::Discard(choice)
+        });
+        assert!(none.is_empty());
+        assert_eq!(asked, 6);
+
+        let mut seen = Vec::new();
+        let odd = select_from_fixed([1, 2, 3, 4, 5]).each_with(|choice| {
+            seen.push(*choice);
+            if *choice % 2 == 1 {
+                Swipe::Keep(choice)
+            } else {
+                Swipe::Discard(choice)
+            }
+        });
+        assert_eq!(odd, [1, 3, 5]);
+        assert_eq!(seen, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn bounds_are_checked_on_both_ends() {
+        let perks = [1, 2, 3, 4];
+
+        let none = select_from_fixed(perks).bounded_with(1..=3, |_| Vec::new());
+        assert_eq!(none.unwrap_err().as_count_out_of_range().unwrap().actual, 0);
+
+        let err = select_from_fixed(perks).bounded_with(1..=3, |c| c.into());
+        let err = *err.unwrap_err().as_count_out_of_range().unwrap();
+        assert_eq!(err.actual, 4);
+        assert_eq!(
+            (err.start, err.end),
+            (Bound::Included(1), Bound::Included(3))
+        );
+
+        assert_eq!(
+            select_from_fixed(perks).bounded_with(..=4, |c| c.into()),
+            Ok(vec![1, 2, 3, 4])
+        );
+        assert_eq!(
+            select_from_fixed(perks).bounded_with(..=4, |_| Vec::new()),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    fn unsatisfiable_bounds_are_rejected_up_front() {
+        let error = select_from_fixed([1, 2, 3, 4])
+            .bounded_with(5.., |_| unreachable!())
+            .unwrap_err();
+
+        let error = error.as_count_out_of_range().unwrap();
+        assert_eq!(error.start, Bound::Included(5));
+        assert_eq!(error.end, Bound::Unbounded);
+        assert_eq!(error.actual, 4);
+
+        // nothing is both more than 1 and less than 2
+        let empty = select_from_fixed([1, 2])
+            .bounded_with((Bound::Excluded(1), Bound::Excluded(2)), |_| unreachable!());
+        assert!(empty.unwrap_err().as_count_out_of_range().is_some());
+    }
+
+    #[test]
+    fn with_rest_keeps_instances_apart() {
+        // equal values, told apart by the tag
+        let hand = [(1, 'a'), (2, 'b'), (1, 'c'), (1, 'd')];
+
+        let (kept, rest) = select_from_fixed(hand).with_rest(|[_, b, c, _]| [c, b]);
+        assert_eq!(kept, [(1, 'c'), (2, 'b')]);
+        assert_eq!(rest[..], [(1, 'a'), (1, 'd')]);
+
+        let (all, none) = select_from_fixed([1, 2]).with_rest(|[a, b]| [b, a]);
+        assert_eq!(all, [2, 1]);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn split_then_chain_round_trips() {
+        let (low, high) = select_from_fixed([1, 2, 3, 4, 5, 6, 7, 8]).split::<4>();
+
+        let rejoined = low.chain(high).any_with(|choices| choices);
+        assert_eq!(rejoined, [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn split_drops_every_value_once() {
+        let values: [Rc<i32>; 4] = std::array::from_fn(|i| Rc::new(i as i32));
+        let handles = values.clone();
+
+        let (left, right) = select_from_fixed(values).split::<1>();
+        // everything is still alive, owned by either half
+        assert!(handles.iter().all(|h| Rc::strong_count(h) == 2));
+
+        drop(left);
+        assert_eq!(Rc::strong_count(&handles[0]), 1);
+        assert!(handles[1..].iter().all(|h| Rc::strong_count(h) == 2));
+
+        drop(right);
+        assert!(handles.iter().all(|h| Rc::strong_count(h) == 1));
+    }
+
+    #[test]
+    fn concat_drops_every_value_once() {
+        let handles: [Rc<i32>; 3] = std::array::from_fn(|i| Rc::new(i as i32));
+        let [a, b, c] = handles.clone();
+
+        let both = select_from_fixed([a]).concat(select_from_fixed([b, c]));
+        assert!(handles.iter().all(|h| Rc::strong_count(h) == 2));
+
+        drop(both);
+        assert!(handles.iter().all(|h| Rc::strong_count(h) == 1));
+    }
+
+    #[test]
+    fn opt_with_picks_and_cancels() {
+        let picked = select_from_fixed([1, 2, 3]).opt_with(|[_, two, _]| Some([two]));
+        assert_eq!(picked, Some([2]));
+
+        let cancelled = select_from_fixed([1, 2, 3]).opt_with::<1, _>(|_| None);
+        assert_eq!(cancelled, None);
+    }
+
+    #[test]
+    fn one_with_returns_the_bare_value() {
+        assert_eq!(select_from_fixed(['x']).one_with(|[x]| x), 'x');
+        assert_eq!(
+            select_from_fixed([String::from("a"), String::from("b")]).one_with(|[_, b]| b),
+            "b"
+        );
+    }
+
+    #[test]
+    fn try_with_returns_values_or_the_error() {
+        let picked = select_from_fixed([1, 2, 3]).try_with(|[a, _, c]| Ok::<_, ()>([c, a]));
+        assert_eq!(picked, Ok([3, 1]));
+
+        let picked = select_from_fixed([1, 2, 3]).try_any_with(|c| Ok::<_, ()>(c.into()));
+        assert_eq!(picked, Ok(vec![1, 2, 3]));
+
+        // on an error every value is dropped, none are handed back
+        let value = Rc::new(());
+        let failed = select_from_fixed([Rc::clone(&value), Rc::clone(&value)])
+            .try_with::<1, _, _>(|_| Err("cancelled"));
+        assert_eq!(failed.unwrap_err(), "cancelled");
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        let failed = select_from_fixed([Rc::clone(&value)]).try_any_with(|_| Err(7));
+        assert_eq!(failed.unwrap_err(), 7);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn opt_with_drops_choices_on_cancel() {
+        let value = Rc::new(());
+        let cancelled = select_from_fixed([Rc::clone(&value)]).opt_with::<1, _>(|_| None);
+
+        assert!(cancelled.is_none());
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn opt_with_recover_hands_back_the_originals() {
+        let value = Rc::new(5);
+        let originals = [Rc::clone(&value), Rc::new(6), Rc::new(7)];
+
+        let recovered = select_from_fixed(originals)
+            .opt_with_recover::<2, _>(|_| None)
+            .unwrap_err();
+        assert!(Rc::ptr_eq(&recovered[0], &value));
+        assert_eq!(recovered.each_ref().map(|r| **r), [5, 6, 7]);
+
+        let picked = select_from_fixed(recovered).opt_with_recover(|[_, b, c]| Some([c, b]));
+        assert_eq!(picked.map(|p| p.map(|r| *r)), Ok([7, 6]));
+        // the unchosen value was dropped
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn exact_with_zero_all_and_mismatch() {
+        assert_eq!(
+            select_from_fixed([0, 1, 2]).exact_with(0, |_| Vec::new()),
+            Ok(vec![])
+        );
+        assert_eq!(
+            select_from_fixed([0, 1, 2]).exact_with(3, |c| c.into()),
+            Ok(vec![0, 1, 2])
+        );
+        assert_eq!(
+            select_from_fixed([0, 1, 2]).exact_with(2, |[a, ..]| vec![a]),
+            Err(WrongSelectionCount {
+                expected: 2,
+                actual: 1
+            }
+            .into())
+        );
+    }
+
+    #[test]
+    fn partition_keeps_original_order() {
+        let (chosen, rest) =
+            select_from_fixed([5, 4, 3, 2, 1]).partition_with(|[a, _, _, d, _]| vec![d, a]);
+
+        assert_eq!(chosen, [2, 5]);
+        assert_eq!(rest, [4, 3, 1]);
+    }
+
+    #[test]
+    fn partition_splits_equal_values_by_instance() {
+        let (chosen, rest) =
+            select_from_fixed([1, 1, 2, 3]).partition_with(|[_, one, ..]| vec![one]);
+
+        assert_eq!(chosen, [1]);
+        assert_eq!(rest, [1, 2, 3]);
+
+        // equal, but distinct allocations
+        let ones = [Rc::new(1), Rc::new(1)];
+        let first = Rc::clone(&ones[0]);
+        let (chosen, rest) = select_from_fixed(ones).partition_with(|[_, second]| vec![second]);
+
+        assert!(Rc::ptr_eq(&rest[0], &first));
+        assert!(!Rc::ptr_eq(&chosen[0], &first));
+    }
+
+    #[test]
+    fn ordered_with_undoes_reversal() {
+        let chosen = select_from_fixed(['a', 'b', 'c']).ordered_with(|[a, b, c]| vec![c, b, a]);
+
+        assert_eq!(chosen, ['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn stored_selector_gives_its_array_back() {
+        struct Turn {
+            options: super::SelectorFixed<3, &'static str>,
+        }
+
+        let turn = Turn {
+            options: select_from_fixed(["draw", "play", "pass"]),
+        };
+
+        assert_eq!(turn.options.iter().count(), 3);
+        assert_eq!(turn.options.into_inner(), ["draw", "play", "pass"]);
+    }
+
+    #[test]
+    fn converted_selectors_choose_like_any_other() {
+        let from_array = SelectorFixed::from([1, 2, 3]);
+        assert_eq!(from_array.with(|[a, _, c]| [c, a]), [3, 1]);
+
+        let values = [4, 5];
+        let from_slice = SelectorFixed::<2, _>::try_from(&values[..]).unwrap();
+        assert_eq!(from_slice.with(|[_, b]| [b]), [5]);
+
+        let too_long = SelectorFixed::<2, i32>::try_from(&[1, 2, 3][..]);
+        assert_eq!(
+            too_long.unwrap_err(),
+            WrongLength {
+                expected: 2,
+                actual: 3
+            }
+        );
+        let too_short = SelectorFixed::<2, i32>::try_from(vec![1]).unwrap_err();
+        assert_eq!(
+            too_short.to_string(),
+            "expected 2 choices, but 1 were given"
+        );
     }
 }
@@ -0,0 +1,208 @@
+//! Selections where the chooser can't change the presented order, and which produce an
+//! attestation of what was presented and chosen. See [frozen](crate::selector::Selector::frozen).
+
+use crate::choice::{IndexedChoice, Slots};
+use crate::postcondition::postcondition;
+use std::hash::{Hash, Hasher};
+
+/// Wraps choices that are presented to the chooser as a shared slice, in exactly the
+/// order they were given.
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct FrozenSelector<T> {
+    choices: Vec<T>,
+}
+
+/// The handle a chooser uses to pick from a frozen presentation, by position in the
+/// presented slice.
+#[derive(Debug)]
+pub struct Picker {
+    picked: Vec<usize>,
+    // whether each presented position was picked, so picking stays O(1)
+    taken: Vec<bool>,
+}
+
+impl Picker {
+    /// Picks the choice at `position` in the presented slice. Returns `false` if the
+    /// position is out of range or was already picked, similar to
+    /// [HashSet::insert](std::collections::HashSet::insert).
+    pub fn pick(&mut self, position: usize) -> bool {
+        match self.taken.get_mut(position) {
+            Some(taken) if !*taken => {
+                *taken = true;
+                self.picked.push(position);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The positions picked so far, in the order they were picked.
+    pub fn picked(&self) -> &[usize] {
+        &self.picked
+    }
+}
+
+/// A record of a frozen selection, meant for audit logs. Two selections have the same
+/// attestation only if the same values were presented in the same order and the same
+/// positions were picked in the same order.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct PresentationAttestation {
+    /// A hash of the presented values, in presentation order. The hash is stable across
+    /// runs and platforms, as long as the values' [Hash] implementations are
+    pub presentation: u64,
+    /// The picked positions in the presented slice, in the order they were picked
+    pub chosen: Vec<usize>,
+}
+
+impl<T> FrozenSelector<T> {
+    pub(crate) fn with_choices(choices: Vec<T>) -> FrozenSelector<T> {
+        FrozenSelector { choices }
+    }
+
+    /// The function `chooser` is shown the choices as a shared slice, which it can inspect
+    /// but not reorder, and picks from them through the [Picker]. The picked values are
+    /// returned in the order they were picked, along with an attestation of the selection.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let tickets = vec![("ann", 17), ("bob", 4), ("cid", 9)];
+    ///
+    /// let (winners, attestation) = select_from(tickets).frozen().with(|choices, picker| {
+    ///     // the draw picks the lowest ticket number
+    ///     let lowest = (0..choices.len()).min_by_key(|&i| choices[i].1).unwrap();
+    ///     picker.pick(lowest);
+    /// });
+    ///
+    /// assert_eq!(winners, [("bob", 4)]);
+    /// assert_eq!(attestation.chosen, [1]);
+    /// ```
+    pub fn with<C>(self, chooser: C) -> (Vec<T>, PresentationAttestation)
+    where
+        T: Hash,
+        C: FnOnce(&[IndexedChoice<'_, T>], &mut Picker),
+    {
+        let total = self.choices.len();
+        let mut slots = Slots::new(self.choices);
+
+        let mut hasher = Fnv::default();
+        let choices = slots.choices();
+        total.hash(&mut hasher);
+        for choice in &choices {
+            (**choice).hash(&mut hasher);
+        }
+
+        let mut picker = Picker {
+            picked: Vec::new(),
+            taken: vec![false; total],
+        };
+        chooser(&choices, &mut picker);
+        drop(choices);
+
+        let chosen = picker.picked;
+        postcondition!(unique_indices(chosen.iter().copied(), total));
+
+        let attestation = PresentationAttestation {
+            presentation: hasher.finish(),
+            chosen: chosen.clone(),
+        };
+
+        (slots.take_all(chosen), attestation)
+    }
+}
+
+// FNV-1a, since the std hashers are randomly keyed or not guaranteed to be stable
+// between releases, which would make attestations impossible to compare later
+struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Fnv {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    // integers are hashed in native byte order by default, fix it so attestations
+    // match across platforms. The signed versions forward to these
+    fn write_u16(&mut self, n: u16) {
+        self.write(&n.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.write(&n.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.write(&n.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, n: u128) {
+        self.write(&n.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.write_u64(n as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::select_from;
+
+    fn attest(values: Vec<&str>, pick: usize) -> super::PresentationAttestation {
+        select_from(values)
+            .frozen()
+            .with(|_, picker| {
+                picker.pick(pick);
+            })
+            .1
+    }
+
+    #[test]
+    fn attestation_is_stable_for_same_presentation() {
+        assert_eq!(
+            attest(vec!["a", "b", "c"], 1),
+            attest(vec!["a", "b", "c"], 1)
+        );
+    }
+
+    #[test]
+    fn attestation_changes_with_presentation_order() {
+        let original = attest(vec!["a", "b", "c"], 1);
+        let reordered = attest(vec!["b", "a", "c"], 1);
+
+        assert_ne!(original.presentation, reordered.presentation);
+        assert_eq!(original.chosen, reordered.chosen);
+    }
+
+    #[test]
+    fn attestation_changes_with_chosen_positions() {
+        assert_ne!(
+            attest(vec!["a", "b", "c"], 1),
+            attest(vec!["a", "b", "c"], 2)
+        );
+    }
+
+    #[test]
+    fn picker_refuses_duplicates_and_out_of_range() {
+        let (chosen, attestation) = select_from(vec![10, 20, 30]).frozen().with(|_, picker| {
+            assert!(picker.pick(2));
+            assert!(!picker.pick(2));
+            assert!(!picker.pick(3));
+            assert!(picker.pick(0));
+        });
+
+        assert_eq!(chosen, [30, 10]);
+        assert_eq!(attestation.chosen, [2, 0]);
+    }
+}
@@ -0,0 +1,220 @@
+//! Selections of exactly one value per group, see [select_one_per_group](crate::select_one_per_group)
+//! and [select_one_per_row](crate::select_one_per_row).
+
+use crate::choice::{IndexedChoice, Slots};
+use crate::error::{ChooseError, WrongGroup, WrongSelectionCount};
+use crate::postcondition::postcondition;
+use std::ops::Range;
+
+/// Wraps named groups of choices, of which exactly one value must be chosen per group.
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct GroupedSelector<G, T> {
+    keys: Vec<G>,
+    groups: Vec<Vec<T>>,
+}
+
+impl<G, T> GroupedSelector<G, T> {
+    pub(crate) fn with_groups<I>(groups: I) -> GroupedSelector<G, T>
+    where
+        I: IntoIterator<Item = (G, Vec<T>)>,
+    {
+        let (keys, groups) = groups.into_iter().unzip();
+        GroupedSelector { keys, groups }
+    }
+
+    /// The function `chooser` is given every group's key along with its choices, and must
+    /// return one choice per group, in group order. All groups share the same guard, so a
+    /// choice from one group could be returned in another group's place, which is rejected
+    /// with a [WrongGroup] error. Returning a different number of choices than there are
+    /// groups is rejected with a [WrongSelectionCount] error. A group without choices can't
+    /// be chosen from, so it always makes the selection fail.
+    /// ```
+    /// use choose_from::select_one_per_group;
+    ///
+    /// let menu = vec![
+    ///     ("entree", vec!["steak", "pasta"]),
+    ///     ("side", vec!["fries"]),
+    ///     ("drink", vec!["water", "soda"]),
+    /// ];
+    ///
+    /// let order = select_one_per_group(menu).with(|groups| {
+    ///     groups.into_iter().map(|(_, mut choices)| choices.pop().unwrap()).collect()
+    /// });
+    ///
+    /// assert_eq!(
+    ///     order.unwrap(),
+    ///     [("entree", "pasta"), ("side", "fries"), ("drink", "soda")]
+    /// );
+    /// ```
+    pub fn with<C>(self, chooser: C) -> Result<Vec<(G, T)>, ChooseError>
+    where
+        C: for<'guard> FnOnce(
+            Vec<(&G, Vec<IndexedChoice<'guard, T>>)>,
+        ) -> Vec<IndexedChoice<'guard, T>>,
+    {
+        let ranges = into_ranges(&self.groups);
+        let total = ranges.last().map_or(0, |range| range.end);
+        let mut slots = Slots::new(self.groups.into_iter().flatten());
+
+        let mut choices = slots.choices().into_iter();
+        let groups = ranges
+            .iter()
+            .map(|range| choices.by_ref().take(range.len()).collect())
+            .collect::<Vec<_>>();
+        let chosen: Vec<usize> = chooser(self.keys.iter().zip(groups).collect())
+            .iter()
+            .map(IndexedChoice::index)
+            .collect();
+        postcondition!(unique_indices(chosen.iter().copied(), total));
+
+        if chosen.len() != ranges.len() {
+            return Err(WrongSelectionCount {
+                expected: ranges.len(),
+                actual: chosen.len(),
+            }
+            .into());
+        }
+        if let Some(group) = (0..ranges.len()).find(|&i| !ranges[i].contains(&chosen[i])) {
+            return Err(WrongGroup { group }.into());
+        }
+
+        Ok(self.keys.into_iter().zip(slots.take_all(chosen)).collect())
+    }
+}
+
+/// Wraps N rows of M choices each, of which exactly one value must be chosen per row.
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct GridSelector<const N: usize, const M: usize, T> {
+    rows: [[T; M]; N],
+}
+
+impl<const N: usize, const M: usize, T> GridSelector<N, M, T> {
+    pub(crate) fn with_rows(rows: [[T; M]; N]) -> GridSelector<N, M, T> {
+        GridSelector { rows }
+    }
+
+    /// The function `chooser` is given the choices row by row, and must return one choice
+    /// per row, in row order. All rows share the same guard, so a choice from one row could
+    /// be returned in another row's place, which is rejected with a [WrongGroup] error naming
+    /// the row. Returning the wrong number of choices doesn't compile.
+    /// ```
+    /// use choose_from::select_one_per_row;
+    ///
+    /// let outfit = select_one_per_row([
+    ///     ["cap", "beanie"],
+    ///     ["shirt", "sweater"],
+    ///     ["boots", "sandals"],
+    /// ])
+    /// .with(|[[cap, _], [_, sweater], [boots, _]]| [cap, sweater, boots]);
+    ///
+    /// assert_eq!(outfit.unwrap(), ["cap", "sweater", "boots"]);
+    /// ```
+    pub fn with<C>(self, chooser: C) -> Result<[T; N], ChooseError>
+    where
+        C: FnOnce([[IndexedChoice<'_, T>; M]; N]) -> [IndexedChoice<'_, T>; N],
+    {
+        let mut slots = Slots::new(self.rows.into_iter().flatten());
+
+        let chosen = chooser(into_grid(slots.choices())).map(|c| c.index());
+        postcondition!(unique_indices(chosen, N * M));
+
+        // a returned choice implies a non-empty row, so M can't be zero here
+        if let Some(row) = (0..N).find(|&row| chosen[row] / M != row) {
+            return Err(WrongGroup { group: row }.into());
+        }
+
+        Ok(chosen.map(|i| slots.take(i)))
+    }
+}
+
+// choices are indexed row by row, so a choice's row is its index divided by the row length
+fn into_grid<const N: usize, const M: usize, C>(choices: Vec<C>) -> [[C; M]; N] {
+    let mut choices = choices.into_iter();
+
+    std::array::from_fn(|_| {
+        std::array::from_fn(|_| choices.next().expect("the grid has N * M choices"))
+    })
+}
+
+// every choice is indexed across all groups, so the range of indices each group covers
+// tells which group a returned choice came from
+fn into_ranges<T>(groups: &[Vec<T>]) -> Vec<Range<usize>> {
+    let mut start = 0;
+
+    groups
+        .iter()
+        .map(|group| {
+            let range = start..start + group.len();
+            start = range.end;
+            range
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{ChooseErrorKind, WrongGroup};
+    use crate::{select_one_per_group, select_one_per_row};
+
+    #[test]
+    fn single_option_groups() {
+        let chosen = select_one_per_group(vec![("a", vec![1]), ("b", vec![2, 3]), ("c", vec![4])])
+            .with(|groups| {
+                groups
+                    .into_iter()
+                    .map(|(_, mut choices)| choices.remove(0))
+                    .collect()
+            });
+
+        assert_eq!(chosen.unwrap(), [("a", 1), ("b", 2), ("c", 4)]);
+    }
+
+    #[test]
+    fn cross_group_returns_are_rejected() {
+        let err = select_one_per_group(vec![("entree", vec![1, 2]), ("side", vec![3])])
+            .with(|groups| {
+                // both choices taken from the entrees
+                let mut entrees = groups.into_iter().next().unwrap().1;
+                vec![entrees.remove(0), entrees.remove(0)]
+            })
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ChooseErrorKind::WrongGroup);
+        assert_eq!(err.as_wrong_group(), Some(&WrongGroup { group: 1 }));
+    }
+
+    #[test]
+    fn missing_groups_are_rejected() {
+        let err = select_one_per_group(vec![("a", vec![1]), ("b", vec![2])])
+            .with(|groups| groups.into_iter().take(1).flat_map(|(_, c)| c).collect())
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ChooseErrorKind::WrongCount);
+    }
+
+    #[test]
+    fn one_value_per_row() {
+        let chosen =
+            select_one_per_row([[1, 2], [3, 4], [5, 6]]).with(|[[_, b], [c, _], [_, f]]| [b, c, f]);
+        assert_eq!(chosen, Ok([2, 3, 6]));
+
+        let empty: Result<[u8; 0], _> = select_one_per_row([[0u8; 3]; 0]).with(|[]| []);
+        assert_eq!(empty, Ok([]));
+    }
+
+    #[test]
+    fn cross_row_returns_are_rejected() {
+        let err = select_one_per_row([["a", "b"], ["c", "d"]])
+            .with(|[[a, b], _]| [a, b])
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ChooseErrorKind::WrongGroup);
+        assert_eq!(err.as_wrong_group(), Some(&WrongGroup { group: 1 }));
+
+        // swapping rows puts both in the wrong place, the first one is reported
+        let err = select_one_per_row([[1], [2]])
+            .with(|[[one], [two]]| [two, one])
+            .unwrap_err();
+        assert_eq!(err.as_wrong_group(), Some(&WrongGroup { group: 0 }));
+    }
+}
@@ -0,0 +1,104 @@
+//! Selecting positions instead of values, see [select_indices_from](crate::select_indices_from).
+
+use crate::choice::{self, IndexedChoice};
+use crate::postcondition::postcondition;
+
+/// Wraps a borrowed slice of choices and provides methods that guarantee selection of
+/// positions within it, leaving the values in place.
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct IndexSelector<'a, T> {
+    choices: &'a [T],
+}
+
+impl<'a, T> IndexSelector<'a, T> {
+    pub(crate) fn with_choices(choices: &'a [T]) -> IndexSelector<'a, T> {
+        IndexSelector { choices }
+    }
+
+    /// The function `chooser` is used to choose from our provided choices by returning a
+    /// K-selection of them, and the positions of those choices in the slice are returned.
+    /// The choices are not `Copy`, so no position can be returned twice:
+    /// ```compile_fail
+    /// use choose_from::select_indices_from;
+    ///
+    /// let chosen = select_indices_from(&[1, 2, 3]).with(|mut choices| {
+    ///     let first = choices.remove(0);
+    ///     [first, first]
+    /// });
+    /// ```
+    /// ```
+    /// use choose_from::select_indices_from;
+    ///
+    /// let mut deck = vec!["ace", "king", "queen"];
+    ///
+    /// let [picked] = select_indices_from(&deck).with(|mut choices| [choices.pop().unwrap()]);
+    /// deck.remove(picked);
+    ///
+    /// assert_eq!(deck, ["ace", "king"]);
+    /// ```
+    pub fn with<const K: usize, C>(self, chooser: C) -> [usize; K]
+    where
+        C: FnOnce(Vec<IndexedChoice<'_, T>>) -> [IndexedChoice<'_, T>; K],
+    {
+        let total = self.choices.len();
+        let chosen = chooser(choice::indexed(self.choices)).map(|c| c.index());
+        postcondition!(unique_indices(chosen, total));
+
+        chosen
+    }
+
+    /// Like [with](IndexSelector::with), but for returning any number of positions.
+    /// ```
+    /// use choose_from::select_indices_from;
+    ///
+    /// let values = [3, 8, 1, 6];
+    ///
+    /// let big = select_indices_from(&values).any_with(|choices| {
+    ///     choices.into_iter().filter(|c| **c > 4).collect()
+    /// });
+    ///
+    /// assert_eq!(big, [1, 3]);
+    /// ```
+    pub fn any_with<C>(self, chooser: C) -> Vec<usize>
+    where
+        C: FnOnce(Vec<IndexedChoice<'_, T>>) -> Vec<IndexedChoice<'_, T>>,
+    {
+        let total = self.choices.len();
+        let chosen: Vec<usize> = chooser(choice::indexed(self.choices))
+            .iter()
+            .map(IndexedChoice::index)
+            .collect();
+        postcondition!(unique_indices(chosen.iter().copied(), total));
+
+        chosen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::select_indices_from;
+
+    #[test]
+    fn indices_after_reordering() {
+        let values = ["a", "b", "c", "d"];
+
+        let chosen = select_indices_from(&values).any_with(|mut choices| {
+            choices.reverse();
+            choices.into_iter().step_by(2).collect()
+        });
+
+        assert_eq!(chosen, [3, 1]);
+        // values are untouched
+        assert_eq!(values, ["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn fixed_number_of_indices() {
+        let chosen = select_indices_from(&[10, 20, 30]).with(|mut choices| {
+            let last = choices.pop().unwrap();
+            [last, choices.remove(0)]
+        });
+
+        assert_eq!(chosen, [2, 0]);
+    }
+}
@@ -99,10 +99,10 @@
 //!     // where chooser is some external function that chooses from the provided suits
 //!     pub fn choose_suit<C>(&self, chooser: C)
 //!     where
-//!         C: FnOnce([Choice<'_, Suit>; N]) -> [Choice<'_, Suit>; 1]
+//!         C: FnOnce([Choice<'_, Suit>; N]) -> Choice<'_, Suit>
 //!     {
 //!         // have user choose some suit (this suit is guaranteed to be from our choices)
-//!         let [suit]: [Suit; 1] = select_from_fixed(self.0).with(chooser);
+//!         let suit: Suit = select_from_fixed(self.0).one_with(chooser);
 //!
 //!         // do stuff with suit
 //!         // ...
@@ -178,11 +178,11 @@
 //! Values are assured to be from the selection through two ways.
 //! First the only constructor for [Choice] is private
 //! ```compile_fail
-//! use choose_from::select_from_fixed;
+//! use choose_from::Choice;
 //!
 //! // we cannot access the private constructor. And it requires a reference
 //! // to a Guard that we cannot construct
-//! let one = Choice::with_guard(1, unreachable!());
+//! let one = Choice::with_guard(1, 0, unreachable!());
 //! ```
 //! So we know choices cannot be created out of thin air (only within this library), but what about the
 //! owned [Choice]s provided to us through [`with`](crate::Selector::with) (or similar methods)?
@@ -190,12 +190,12 @@
 //! for a new [select_from] with the same type, then we could return values that aren't from the
 //! available choices! If we try to do that:
 //! ```compile_fail
-//! use choose_from::select_from_fixed;
+//! use choose_from::select_from;
 //!
 //! let mut smuggler = Vec::new();
-//! select_from(vec![1, 2, 3, 4]).any_with(|choices| {
+//! select_from(vec![1, 2, 3, 4]).any_with(|mut choices| {
 //!     // try to move last three values out of the closure
-//!     smuggle.extend(choices.drain(1..));
+//!     smuggler.extend(choices.drain(1..));
 //!     choices
 //! });
 //!
@@ -213,15 +213,70 @@
 //! function *MUST* select value(s) from the provided ones.
 //!
 //! If you are interested in learning more try reading the code, it is quite simple.
+//!
+//! # Features
+//!
+//! The crate has no required dependencies and no default features, everything else is opt-in:
+//!
+//! - `nightly`: adds fully fixed-size variants of [SelectorFixed::concat],
+//!   [SelectorFixed::split], [SelectorFixed::with_rest] and [SelectorFixed::reject_with]
+//!   (`concat_fixed`, `split_fixed`, `with_rest_fixed` and `reject_with_fixed`) using const
+//!   generic arithmetic, and adds the `owned::NotShared` marker for requiring
+//!   choices without shared handles. Requires a nightly compiler.
+//! - `rand`: adds random choosers like `Selector::choose_random` and shuffled presentations
+//!   with `Selector::shuffled`, built on the `rand` crate.
 
-mod choice;
+#![cfg_attr(feature = "nightly", allow(incomplete_features))]
+#![cfg_attr(feature = "nightly", feature(generic_const_exprs))]
+#![cfg_attr(
+    feature = "nightly",
+    feature(allocator_api, auto_traits, negative_impls)
+)]
+
+pub mod choice;
+pub mod choosers;
+pub mod draft;
+pub mod error;
 pub mod fixed;
+pub mod frozen;
+pub mod groups;
+pub mod indices;
+pub mod limits;
+pub mod mapped;
+#[cfg(test)]
+mod memory_tests;
+pub mod nested;
+pub mod nonempty;
+#[cfg(feature = "nightly")]
+pub mod owned;
+mod postcondition;
+pub mod prelude;
+#[cfg(feature = "rand")]
+mod random;
 pub mod selector;
+#[cfg(feature = "rand")]
+pub mod shuffled;
+pub mod swipe;
+mod tournament;
+pub mod tree;
+mod tuples;
+pub mod weighted;
+pub mod zip;
 
 pub use choice::Choice;
 use choice::Guard;
+use error::EmptyChoices;
 use fixed::SelectorFixed;
+use groups::{GridSelector, GroupedSelector};
+use indices::IndexSelector;
+use limits::LimitedSelector;
+use nested::NestedSelector;
+use nonempty::NonEmptySelector;
 use selector::Selector;
+use std::slice;
+use std::sync::Arc;
+use tree::{Expand, TreeSelector};
+use weighted::WeightedSelector;
 
 /// Wraps our arbitrary number of choices and allows us to force a function/closure to
 /// choose from them
@@ -249,6 +304,225 @@ where
     Selector::with_choices(choices)
 }
 
+/// Like [select_from], but chooses references into a borrowed slice, leaving the slice
+/// untouched. The returned references borrow from the slice, so no value needs to be
+/// cloned or moved.
+/// ```
+/// use choose_from::select_from_ref;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Config {
+///     name: &'static str,
+///     threads: usize,
+/// }
+///
+/// let configs = [
+///     Config { name: "small", threads: 2 },
+///     Config { name: "medium", threads: 8 },
+///     Config { name: "large", threads: 32 },
+/// ];
+///
+/// let [a, b] = select_from_ref(&configs).with(|mut choices| {
+///     let large = choices.pop().unwrap();
+///     [choices.remove(0), large]
+/// });
+///
+/// assert_eq!([a.name, b.name], ["small", "large"]);
+/// assert!(std::ptr::eq(a, &configs[0]));
+/// ```
+/// The returned references can't outlive the slice:
+/// ```compile_fail
+/// use choose_from::select_from_ref;
+///
+/// let chosen = {
+///     let configs = vec![String::from("small"), String::from("large")];
+///     let [chosen] = select_from_ref(&configs).with(|mut choices| [choices.remove(0)]);
+///     chosen
+/// };
+/// ```
+/// And like every other selection, the choices themselves can't escape the chooser:
+/// ```compile_fail
+/// use choose_from::select_from_ref;
+///
+/// let configs = [1, 2, 3];
+/// let mut smuggler = Vec::new();
+///
+/// select_from_ref(&configs).any_with(|mut choices| {
+///     smuggler.push(choices.remove(0));
+///     choices
+/// });
+/// ```
+pub fn select_from_ref<T>(choices: &[T]) -> Selector<slice::Iter<'_, T>, &T> {
+    Selector::with_choices(choices.iter())
+}
+
+/// Like [select_from_ref], but chooses mutable references into a borrowed slice, so the
+/// chosen elements can be changed in place once the chooser returns. Every choice is a
+/// different element, and a choice can't be returned twice, so the returned references
+/// never alias.
+/// ```
+/// use choose_from::select_from_mut;
+///
+/// let mut health = [10, 7, 12, 3];
+///
+/// // choose two units to damage
+/// let [a, b] = select_from_mut(&mut health).with(|choices| {
+///     let mut weakest: Vec<_> = choices.into_iter().filter(|c| ***c < 11).collect();
+///     [weakest.remove(0), weakest.remove(0)]
+/// });
+///
+/// *a -= 5;
+/// *b -= 5;
+///
+/// assert_eq!(health, [5, 2, 12, 3]);
+/// ```
+/// Returning the same element twice is a compile time error:
+/// ```compile_fail
+/// use choose_from::select_from_mut;
+///
+/// let mut health = [10, 7];
+///
+/// let [a, b] = select_from_mut(&mut health).with(|mut choices| {
+///     let first = choices.remove(0);
+///     [first, first]
+/// });
+/// ```
+pub fn select_from_mut<T>(choices: &mut [T]) -> Selector<slice::IterMut<'_, T>, &mut T> {
+    Selector::with_choices(choices.iter_mut())
+}
+
+/// Like [select_from], but checks up front that there is at least one choice, so that
+/// choosing one value can't fail later. See [NonEmptySelector].
+/// ```
+/// use choose_from::select_from_nonempty;
+/// use choose_from::error::EmptyChoices;
+///
+/// let none = select_from_nonempty(Vec::<&str>::new());
+/// assert_eq!(none, Err(EmptyChoices));
+///
+/// let targets = select_from_nonempty(vec!["orc", "troll"]).unwrap();
+/// assert_eq!(targets.one_with(|mut c| c.remove(1)), "troll");
+/// ```
+pub fn select_from_nonempty<I, T>(choices: I) -> Result<NonEmptySelector<T>, EmptyChoices>
+where
+    I: IntoIterator<Item = T>,
+{
+    Selector::with_choices(choices).try_into_nonempty()
+}
+
+/// Wraps choices that may each be chosen up to a number of times, given alongside each
+/// value. The chooser makes extra copies with [LimitedChoice::duplicate](limits::LimitedChoice::duplicate),
+/// which fails once a value's limit is reached. A limit of zero means the value can't be
+/// chosen, so it isn't presented at all.
+/// ```
+/// use choose_from::select_from_with_limits;
+///
+/// // basic lands up to 4 copies, everything else once
+/// let deck = select_from_with_limits(vec![(4, "plains"), (1, "angel")]).any_with(|choices| {
+///     let mut deck: Vec<_> = std::iter::from_fn(|| choices[0].duplicate()).collect();
+///     deck.extend(choices);
+///     deck
+/// });
+///
+/// assert_eq!(deck, ["plains", "plains", "plains", "plains", "angel"]);
+/// ```
+pub fn select_from_with_limits<T: Clone>(items: Vec<(usize, T)>) -> LimitedSelector<T> {
+    LimitedSelector::with_items(items)
+}
+
+/// Like [select_from], but for choices behind shared [Arc] pointers. The chooser is handed
+/// clones of the `Arc`s, so the original slice is left untouched.
+/// ```
+/// use std::sync::Arc;
+/// use choose_from::select_from_shared;
+///
+/// let pool = vec![Arc::new(1), Arc::new(2), Arc::new(3)];
+///
+/// let [chosen] = select_from_shared(&pool).with(|mut choices| [choices.pop().unwrap()]);
+///
+/// assert!(Arc::ptr_eq(&chosen, &pool[2]));
+/// assert_eq!(pool.len(), 3);
+/// ```
+pub fn select_from_shared<T>(shared: &[Arc<T>]) -> Selector<Vec<Arc<T>>, Arc<T>> {
+    Selector::with_choices(shared.to_vec())
+}
+
+/// Like [select_from], but every value comes paired with a weight (a probability, a
+/// priority, or anything else). The chooser gets [WeightedChoice](choice::WeightedChoice)s,
+/// which show both, and the chosen values are returned still paired with their weights, see
+/// [WeightedSelector].
+/// ```
+/// use choose_from::select_from_weighted;
+///
+/// let loot = vec![("sword", 0.5_f64), ("shield", 0.2), ("potion", 0.9)];
+///
+/// let [first, second] = select_from_weighted(loot).with(|mut choices| {
+///     choices.sort_by(|a, b| b.weight().total_cmp(a.weight()));
+///     [choices.remove(0), choices.remove(0)]
+/// });
+///
+/// assert_eq!([first, second], [("potion", 0.9), ("sword", 0.5)]);
+/// ```
+pub fn select_from_weighted<I, T, W>(choices: I) -> WeightedSelector<I, T, W>
+where
+    I: IntoIterator<Item = (T, W)>,
+{
+    WeightedSelector::with_choices(choices)
+}
+
+/// Wraps named groups of choices and allows us to force a function/closure to choose
+/// exactly one value from each group. The chosen values are returned with their group's
+/// key, in group order, see [GroupedSelector::with].
+/// ```
+/// use choose_from::select_one_per_group;
+///
+/// let loadout = select_one_per_group(vec![("weapon", vec!["axe", "bow"]), ("armor", vec!["mail"])])
+///     .with(|groups| groups.into_iter().map(|(_, mut c)| c.remove(0)).collect())
+///     .unwrap();
+///
+/// assert_eq!(loadout, [("weapon", "axe"), ("armor", "mail")]);
+/// ```
+pub fn select_one_per_group<I, G, T>(groups: I) -> GroupedSelector<G, T>
+where
+    I: IntoIterator<Item = (G, Vec<T>)>,
+{
+    GroupedSelector::with_groups(groups)
+}
+
+/// Wraps a grid of N rows with M choices each, and allows us to force a function/closure
+/// to choose exactly one value from each row. The chosen values are returned in row order,
+/// see [GridSelector::with].
+/// ```
+/// use choose_from::select_one_per_row;
+///
+/// // (size, crust, topping) rows
+/// let pizza = select_one_per_row([["small", "large"], ["thin", "deep"], ["ham", "olive"]])
+///     .with(|[[_, large], [thin, _], [_, olive]]| [large, thin, olive])
+///     .unwrap();
+///
+/// assert_eq!(pizza, ["large", "thin", "olive"]);
+/// ```
+pub fn select_one_per_row<const N: usize, const M: usize, T>(
+    grid: [[T; M]; N],
+) -> GridSelector<N, M, T> {
+    GridSelector::with_rows(grid)
+}
+
+/// Wraps a borrowed slice of choices and allows us to force a function/closure to choose
+/// positions within it, without moving any values out.
+/// ```
+/// use choose_from::select_indices_from;
+///
+/// let names = ["Ann", "Bob", "Cid"];
+///
+/// let [picked] = select_indices_from(&names).with(|mut choices| [choices.remove(1)]);
+///
+/// assert_eq!(names[picked], "Bob");
+/// ```
+pub fn select_indices_from<T>(choices: &[T]) -> IndexSelector<'_, T> {
+    IndexSelector::with_choices(choices)
+}
+
 /// Wraps our fixed number of choices and allows us to force a function/closure to choose
 /// from them
 /// ```
@@ -267,6 +541,98 @@ pub fn select_from_fixed<const N: usize, T>(choices: [T; N]) -> SelectorFixed<N,
     SelectorFixed::with_choices(choices)
 }
 
+/// Like [select_from_fixed], but chooses references into a borrowed array, leaving the
+/// array untouched, see [select_from_ref].
+/// ```
+/// use choose_from::select_from_fixed_ref;
+///
+/// struct Table {
+///     cards: [String; 3],
+/// }
+///
+/// let table = Table {
+///     cards: ["ace".to_string(), "king".to_string(), "two".to_string()],
+/// };
+///
+/// let [high, low] = select_from_fixed_ref(&table.cards).with(|[ace, _, two]| [ace, two]);
+///
+/// assert_eq!([high, low], ["ace", "two"]);
+/// assert!(std::ptr::eq(high, &table.cards[0]));
+/// ```
+/// The returned references can't outlive the array:
+/// ```compile_fail
+/// use choose_from::select_from_fixed_ref;
+///
+/// let chosen = {
+///     let cards = [String::from("ace"), String::from("king")];
+///     let [chosen] = select_from_fixed_ref(&cards).with(|[ace, _]| [ace]);
+///     chosen
+/// };
+/// ```
+/// And the choices can't escape the chooser:
+/// ```compile_fail
+/// use choose_from::select_from_fixed_ref;
+///
+/// let cards = [1, 2];
+/// let mut smuggler = Vec::new();
+///
+/// select_from_fixed_ref(&cards).with(|[one, two]| {
+///     smuggler.push(one);
+///     [two]
+/// });
+/// ```
+pub fn select_from_fixed_ref<const N: usize, T>(choices: &[T; N]) -> SelectorFixed<N, &T> {
+    SelectorFixed::with_choices(choices.each_ref())
+}
+
+/// Wraps outer choices whose inner choices are expensive to build, and allows us to force
+/// a function/closure to choose one outer value and then choose from only its inner ones.
+/// The thunk producing the inner choices is only called for the chosen outer value.
+/// ```
+/// use choose_from::select_then;
+///
+/// let categories: Vec<(_, fn() -> Vec<i32>)> =
+///     vec![("evens", || vec![2, 4, 6]), ("odds", || vec![1, 3, 5])];
+///
+/// let (category, chosen) = select_then(categories).with(
+///     |mut choices| choices.remove(0),
+///     |choices| choices.into_iter().take(2).collect(),
+/// );
+///
+/// assert_eq!((category, chosen), ("evens", vec![2, 4]));
+/// ```
+pub fn select_then<A, B, F>(choices: Vec<(A, F)>) -> NestedSelector<A, F>
+where
+    F: FnOnce() -> Vec<B>,
+{
+    NestedSelector::with_choices(choices)
+}
+
+/// Wraps the roots of a selection tree, where choosing a value can open up a new selection
+/// of its [children](Expand::children). Paths are chosen with [TreeSelector::with].
+/// ```
+/// use choose_from::select_tree_from;
+/// use choose_from::tree::Expand;
+///
+/// struct Leaf(u32);
+///
+/// impl Expand for Leaf {
+///     fn children(&self) -> Option<Vec<Leaf>> {
+///         None
+///     }
+/// }
+///
+/// let path = select_tree_from(vec![Leaf(1), Leaf(2)])
+///     .with(|_, mut choices| choices.remove(0))
+///     .unwrap();
+///
+/// assert_eq!(path.len(), 1);
+/// assert_eq!(path[0].0, 1);
+/// ```
+pub fn select_tree_from<T: Expand>(roots: Vec<T>) -> TreeSelector<T> {
+    TreeSelector::with_roots(roots)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -299,5 +665,98 @@ mod tests {
         ["b", "c"]
     );
 
+    #[test]
+    fn fixed_ref_selection_borrows_without_moving() {
+        let values = [String::from("a"), String::from("b"), String::from("c")];
+
+        let chosen = select_from_fixed_ref(&values).any_with(|[a, _, c]| vec![c, a]);
+
+        assert!(std::ptr::eq(chosen[0], &values[2]));
+        assert!(std::ptr::eq(chosen[1], &values[0]));
+        assert_eq!(values, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn shared_selection_is_exactly_returned_choices() {
+        let pool: Vec<Arc<i32>> = (0..5).map(Arc::new).collect();
+        let mut stash = Vec::new();
+
+        let chosen = select_from_shared(&pool).any_with(|choices| {
+            // sharing every value doesn't give us any new choices to return
+            stash.extend(choices.iter().map(|c| c.share()));
+            choices.into_iter().skip(3).collect()
+        });
+
+        assert_eq!(stash.len(), 5);
+        assert_eq!(chosen.len(), 2);
+        assert!(Arc::ptr_eq(&chosen[0], &pool[3]));
+        assert!(Arc::ptr_eq(&chosen[1], &pool[4]));
+        // pool + stash + chosen
+        assert_eq!(Arc::strong_count(&pool[3]), 3);
+        assert_eq!(Arc::strong_count(&pool[0]), 2);
+    }
+
+    #[test]
+    fn weighted_choices_keep_their_original_weights() {
+        let jobs = vec![("build", 3), ("test", 7), ("deploy", 1), ("lint", 7)];
+
+        let chosen = select_from_weighted(jobs.clone()).any_with(|mut choices| {
+            choices.retain(|c| *c.weight() > 2);
+            choices.reverse();
+            choices
+        });
+
+        assert_eq!(chosen, [("lint", 7), ("test", 7), ("build", 3)]);
+        assert!(chosen.iter().all(|pair| jobs.contains(pair)));
+    }
+
+    // written once, usable from both selectors
+    fn evens<'guard>(
+        choices: impl IntoIterator<Item = Choice<'guard, i32>>,
+    ) -> Vec<Choice<'guard, i32>> {
+        choices.into_iter().filter(|c| **c % 2 == 0).collect()
+    }
+
+    // a plain fn item can be passed directly, since its lifetime is higher-ranked
+    fn firsts<'guard>(mut choices: Vec<Choice<'guard, i32>>) -> Vec<Choice<'guard, i32>> {
+        choices.truncate(1);
+        choices
+    }
+
+    #[test]
+    fn same_chooser_for_both_selectors() {
+        assert_eq!(select_from(vec![1, 2, 3, 4]).any_with(|c| evens(c)), [2, 4]);
+        assert_eq!(
+            select_from_fixed([1, 2, 3, 4]).any_with(|c| evens(c)),
+            [2, 4]
+        );
+
+        assert_eq!(select_from(vec![1, 2]).any_with(firsts), [1]);
+        assert_eq!(
+            select_from_fixed([1, 2]).any_with(|c| firsts(c.into())),
+            [1]
+        );
+    }
+
+    #[test]
+    fn mutable_selection_changes_only_chosen_elements() {
+        let mut units = vec![
+            String::from("archer"),
+            String::from("knight"),
+            String::from("mage"),
+        ];
+
+        let [first, last] = select_from_mut(&mut units).with(|mut choices| {
+            let last = choices.pop().unwrap();
+            [choices.remove(0), last]
+        });
+
+        assert!(!std::ptr::eq(&*first, &*last));
+        first.push_str(" (hit)");
+        last.push_str(" (hit)");
+
+        assert_eq!(units, ["archer (hit)", "knight", "mage (hit)"]);
+    }
+
     // TODO: write more tests
 }
@@ -0,0 +1,173 @@
+//! Selections where each value may be chosen up to a limited number of times, see
+//! [select_from_with_limits](crate::select_from_with_limits).
+
+use std::cell::Cell;
+use std::ops::Deref;
+
+/// Wraps choices that each have a maximum number of copies that can be chosen.
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct LimitedSelector<T> {
+    items: Vec<(usize, T)>,
+}
+
+/// A choice that can be duplicated, up to the limit of its value. Every copy is a choice
+/// of its own, so a chooser can only ever return as many copies as the limit allows.
+#[derive(Debug)]
+pub struct LimitedChoice<'guard, T> {
+    value: &'guard T,
+    index: usize,
+    // shared by every copy of the same value
+    remaining: &'guard Cell<usize>,
+}
+
+impl<'guard, T> Deref for LimitedChoice<'guard, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.value
+    }
+}
+
+impl<'guard, T> LimitedChoice<'guard, T> {
+    /// How many more copies of this value can be made with [duplicate](LimitedChoice::duplicate).
+    pub fn remaining_copies(&self) -> usize {
+        self.remaining.get()
+    }
+
+    /// Makes another copy of this choice, or returns `None` if the limit of the value has
+    /// been reached.
+    /// ```
+    /// use choose_from::select_from_with_limits;
+    ///
+    /// let chosen = select_from_with_limits(vec![(2, "forest"), (1, "dragon")]).any_with(|choices| {
+    ///     let forest = &choices[0];
+    ///     let copy = forest.duplicate().unwrap();
+    ///
+    ///     // the limit of 2 is reached
+    ///     assert!(forest.duplicate().is_none());
+    ///     assert_eq!(choices[1].remaining_copies(), 0);
+    ///
+    ///     let mut chosen = choices;
+    ///     chosen.push(copy);
+    ///     chosen
+    /// });
+    ///
+    /// assert_eq!(chosen, ["forest", "dragon", "forest"]);
+    /// ```
+    pub fn duplicate(&self) -> Option<LimitedChoice<'guard, T>> {
+        let remaining = self.remaining.get().checked_sub(1)?;
+        self.remaining.set(remaining);
+
+        Some(LimitedChoice {
+            value: self.value,
+            index: self.index,
+            remaining: self.remaining,
+        })
+    }
+
+    /// The position of this choice's value in the original choices.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<T: Clone> LimitedSelector<T> {
+    pub(crate) fn with_items(items: Vec<(usize, T)>) -> LimitedSelector<T> {
+        LimitedSelector { items }
+    }
+
+    /// The function `chooser` is given one choice for each value with a limit above zero,
+    /// and returns any number of choices, including copies made with
+    /// [duplicate](LimitedChoice::duplicate). The values are cloned for every returned copy.
+    pub fn any_with<C>(self, chooser: C) -> Vec<T>
+    where
+        C: for<'guard> FnOnce(Vec<LimitedChoice<'guard, T>>) -> Vec<LimitedChoice<'guard, T>>,
+    {
+        let remaining = self.remaining();
+        let choices = self.choices(&remaining);
+
+        chooser(choices)
+            .into_iter()
+            .map(|c| c.value.clone())
+            .collect()
+    }
+
+    /// Like [any_with](LimitedSelector::any_with), but for choosing exactly K copies.
+    /// ```
+    /// use choose_from::select_from_with_limits;
+    ///
+    /// let [a, b] = select_from_with_limits(vec![(4, "island")]).with(|mut choices| {
+    ///     let island = choices.pop().unwrap();
+    ///     [island.duplicate().unwrap(), island]
+    /// });
+    ///
+    /// assert_eq!([a, b], ["island", "island"]);
+    /// ```
+    pub fn with<const K: usize, C>(self, chooser: C) -> [T; K]
+    where
+        C: for<'guard> FnOnce(Vec<LimitedChoice<'guard, T>>) -> [LimitedChoice<'guard, T>; K],
+    {
+        let remaining = self.remaining();
+        let choices = self.choices(&remaining);
+
+        chooser(choices).map(|c| c.value.clone())
+    }
+
+    // the presented choice is the first copy, so only the rest can be duplicated
+    fn remaining(&self) -> Vec<Cell<usize>> {
+        self.items
+            .iter()
+            .map(|(limit, _)| Cell::new(limit.saturating_sub(1)))
+            .collect()
+    }
+
+    fn choices<'a>(&'a self, remaining: &'a [Cell<usize>]) -> Vec<LimitedChoice<'a, T>> {
+        self.items
+            .iter()
+            .zip(remaining)
+            .enumerate()
+            .filter(|(_, ((limit, _), _))| *limit > 0)
+            .map(|(index, ((_, value), remaining))| LimitedChoice {
+                value,
+                index,
+                remaining,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::select_from_with_limits;
+
+    #[test]
+    fn exhausted_limit_leaves_others_unaffected() {
+        let chosen = select_from_with_limits(vec![(3, 'a'), (2, 'b')]).any_with(|choices| {
+            let copies: Vec<_> = std::iter::from_fn(|| choices[0].duplicate()).collect();
+            assert_eq!(copies.len(), 2);
+            assert!(choices[0].duplicate().is_none());
+            assert_eq!(choices[0].remaining_copies(), 0);
+
+            assert_eq!(choices[1].remaining_copies(), 1);
+            let b = choices[1].duplicate().unwrap();
+
+            let mut chosen = copies;
+            chosen.push(b);
+            chosen.extend(choices);
+            chosen
+        });
+
+        assert_eq!(chosen, ['a', 'a', 'b', 'a', 'b']);
+    }
+
+    #[test]
+    fn zero_limit_is_not_presented() {
+        let chosen = select_from_with_limits(vec![(0, 1), (1, 2)]).any_with(|choices| {
+            assert_eq!(choices.len(), 1);
+            assert_eq!(choices[0].index(), 1);
+            choices
+        });
+
+        assert_eq!(chosen, [2]);
+    }
+}
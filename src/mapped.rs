@@ -0,0 +1,212 @@
+//! Choosing from a view of the values, see [map_choices](crate::selector::Selector::map_choices)
+//! and [map_view](crate::fixed::SelectorFixed::map_view).
+
+use crate::choice::{self, IndexedChoice, Slots};
+use crate::fixed::Fits;
+use crate::postcondition::postcondition;
+
+/// Wraps choices together with a view of each one. The chooser only ever sees the views,
+/// but the original values are returned for whatever views it chose.
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct MappedSelector<T, U> {
+    originals: Vec<T>,
+    views: Vec<U>,
+}
+
+impl<T, U> MappedSelector<T, U> {
+    pub(crate) fn with_views(originals: Vec<T>, views: Vec<U>) -> MappedSelector<T, U> {
+        MappedSelector { originals, views }
+    }
+
+    /// The function `chooser` is used to choose from the views by returning a K-selection
+    /// of them, and the original values behind the chosen views are returned. Each view is
+    /// tied to its value by position, so the returned values are always among the originals.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// struct Account {
+    ///     id: u64,
+    ///     name: &'static str,
+    ///     balance: u32,
+    /// }
+    ///
+    /// let accounts = vec![
+    ///     Account { id: 7, name: "savings", balance: 100 },
+    ///     Account { id: 9, name: "checking", balance: 20 },
+    /// ];
+    ///
+    /// // the chooser only learns the names
+    /// let [picked] = select_from(accounts)
+    ///     .map_choices(|account| account.name)
+    ///     .with(|mut choices| [choices.pop().unwrap()]);
+    ///
+    /// assert_eq!(picked.id, 9);
+    /// assert_eq!(picked.balance, 20);
+    /// ```
+    pub fn with<const K: usize, C>(self, chooser: C) -> [T; K]
+    where
+        C: FnOnce(Vec<IndexedChoice<'_, U>>) -> [IndexedChoice<'_, U>; K],
+    {
+        let total = self.originals.len();
+        let mut slots = Slots::new(self.originals);
+
+        let chosen = chooser(choice::indexed(&self.views)).map(|c| c.index());
+        postcondition!(unique_indices(chosen, total));
+
+        chosen.map(|i| slots.take(i))
+    }
+
+    /// Like [with](MappedSelector::with), but for choosing any number of views.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// // (secret, label)
+    /// let keys = vec![("hunter2", "work"), ("swordfish", "home"), ("letmein", "work")];
+    ///
+    /// let work = select_from(keys)
+    ///     .map_choices(|key| key.1)
+    ///     .any_with(|choices| choices.into_iter().filter(|c| **c == "work").collect());
+    ///
+    /// assert_eq!(work, [("hunter2", "work"), ("letmein", "work")]);
+    /// ```
+    pub fn any_with<C>(self, chooser: C) -> Vec<T>
+    where
+        C: FnOnce(Vec<IndexedChoice<'_, U>>) -> Vec<IndexedChoice<'_, U>>,
+    {
+        let total = self.originals.len();
+        let mut slots = Slots::new(self.originals);
+
+        let chosen: Vec<usize> = chooser(choice::indexed(&self.views))
+            .iter()
+            .map(IndexedChoice::index)
+            .collect();
+        postcondition!(unique_indices(chosen.iter().copied(), total));
+
+        slots.take_all(chosen)
+    }
+}
+
+/// Like [MappedSelector], but for a fixed number of choices, so the chooser is handed
+/// its views as an array.
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct MappedSelectorFixed<const N: usize, T, U> {
+    originals: [T; N],
+    views: [U; N],
+}
+
+impl<const N: usize, T, U> MappedSelectorFixed<N, T, U> {
+    pub(crate) fn with_views(originals: [T; N], views: [U; N]) -> MappedSelectorFixed<N, T, U> {
+        MappedSelectorFixed { originals, views }
+    }
+
+    /// Like [SelectorFixed::with](crate::SelectorFixed::with), but the chooser picks from the
+    /// views, and the original values behind the chosen views are returned.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    ///
+    /// let [secret] = select_from_fixed([("hunter2", "work"), ("swordfish", "home")])
+    ///     .map_view(|key| key.1)
+    ///     .with(|[_, home]| [home]);
+    ///
+    /// assert_eq!(secret, ("swordfish", "home"));
+    /// ```
+    pub fn with<const K: usize, C>(self, chooser: C) -> [T; K]
+    where
+        C: FnOnce([IndexedChoice<'_, U>; N]) -> [IndexedChoice<'_, U>; K],
+    {
+        let () = Fits::<K, N>::OK;
+
+        let mut slots = Slots::new(self.originals);
+
+        let chosen = chooser(choice::indexed_fixed(&self.views)).map(|c| c.index());
+        postcondition!(unique_indices(chosen, N));
+
+        chosen.map(|i| slots.take(i))
+    }
+
+    /// Like [with](MappedSelectorFixed::with), but for choosing any number of views.
+    pub fn any_with<C>(self, chooser: C) -> Vec<T>
+    where
+        C: FnOnce([IndexedChoice<'_, U>; N]) -> Vec<IndexedChoice<'_, U>>,
+    {
+        let mut slots = Slots::new(self.originals);
+
+        let chosen: Vec<usize> = chooser(choice::indexed_fixed(&self.views))
+            .iter()
+            .map(IndexedChoice::index)
+            .collect();
+        postcondition!(unique_indices(chosen.iter().copied(), N));
+
+        slots.take_all(chosen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{select_from, select_from_fixed};
+
+    #[derive(Debug, PartialEq)]
+    struct User {
+        id: u32,
+        name: String,
+        password: String,
+    }
+
+    fn user(id: u32, name: &str) -> User {
+        User {
+            id,
+            name: name.to_string(),
+            password: format!("{name}-secret"),
+        }
+    }
+
+    #[test]
+    fn redacted_view_returns_full_structs() {
+        let users = vec![user(1, "ann"), user(2, "bob"), user(3, "cid")];
+
+        let chosen = select_from(users)
+            .map_choices(|u| u.name.clone())
+            .any_with(|mut choices| {
+                // the chooser reorders, which must not mix up which user is returned
+                choices.reverse();
+                choices
+                    .into_iter()
+                    .filter(|c| c.as_str() != "bob")
+                    .collect()
+            });
+
+        assert_eq!(chosen, [user(3, "cid"), user(1, "ann")]);
+        assert_eq!(chosen[0].password, "cid-secret");
+    }
+
+    #[test]
+    fn equal_views_return_their_own_originals() {
+        let [picked] = select_from(vec![user(1, "ann"), user(2, "ann")])
+            .map_choices(|u| u.name.clone())
+            .with(|mut choices| [choices.remove(1)]);
+
+        assert_eq!(picked.id, 2);
+    }
+
+    #[test]
+    fn fixed_maps_return_views_or_originals() {
+        let users = [
+            user(1, "ann"),
+            user(2, "bob"),
+            user(3, "ann"),
+            user(4, "dee"),
+        ];
+
+        // a plain map returns the mapped values
+        let ids = select_from_fixed(users.each_ref().map(|u| u.id))
+            .map(|id| id * 10)
+            .any_with(|choices| choices.into_iter().filter(|c| **c > 20).collect());
+        assert_eq!(ids, [30, 40]);
+
+        // a view returns the originals, even when views are equal
+        let chosen = select_from_fixed(users)
+            .map_view(|u| u.name.clone())
+            .with(|[_, b, ann, _]| [ann, b]);
+        assert_eq!(chosen, [user(3, "ann"), user(2, "bob")]);
+    }
+}
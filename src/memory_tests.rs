@@ -0,0 +1,209 @@
+// Memory-safety regression tests, meant to be run under Miri as well as normally
+// (`cargo +nightly miri test`). Each selection path that moves values around is exercised
+// with element types that make mistakes visible: double drops, leaks, misalignment,
+// and panics while dropping.
+
+use crate::{select_from, select_from_fixed};
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+
+/// Size for tests that would otherwise be too slow under Miri.
+pub(crate) const LARGE_N: usize = if cfg!(miri) { 64 } else { 10_000 };
+
+/// Counts how many times values sharing a counter were dropped.
+#[derive(Debug)]
+pub(crate) struct DropCounter {
+    pub(crate) id: usize,
+    drops: Rc<Cell<usize>>,
+}
+
+impl DropCounter {
+    pub(crate) fn many(n: usize) -> (Vec<DropCounter>, Rc<Cell<usize>>) {
+        let drops = Rc::new(Cell::new(0));
+        let values = (0..n)
+            .map(|id| DropCounter {
+                id,
+                drops: Rc::clone(&drops),
+            })
+            .collect();
+
+        (values, drops)
+    }
+}
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+/// Panics when it is the nth value (counting from 1) sharing a counter to be dropped.
+#[derive(Debug)]
+pub(crate) struct PanicOnNthDrop {
+    n: usize,
+    drops: Rc<Cell<usize>>,
+}
+
+impl Drop for PanicOnNthDrop {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+        if self.drops.get() == self.n {
+            panic!("dropped value number {}", self.n);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(align(64))]
+pub(crate) struct OverAligned(pub(crate) u8);
+
+#[test]
+fn with_drops_unchosen_once() {
+    let (values, drops) = DropCounter::many(5);
+
+    let chosen = select_from(values).with(|mut choices| [choices.remove(3), choices.remove(0)]);
+    assert_eq!(drops.get(), 3);
+    assert_eq!([chosen[0].id, chosen[1].id], [3, 0]);
+
+    drop(chosen);
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn lazy_choices_dropped_early_drop_the_rest_once() {
+    let (values, drops) = DropCounter::many(5);
+
+    let [chosen] = select_from(values).with_iter(|mut choices| [choices.nth(1).unwrap()]);
+    assert_eq!(drops.get(), 4);
+    assert_eq!(chosen.id, 1);
+
+    drop(chosen);
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn fixed_with_drops_unchosen_once() {
+    let (values, drops) = DropCounter::many(4);
+    let values: [DropCounter; 4] = values.try_into().unwrap();
+
+    let [chosen] = select_from_fixed(values).with(|[_, b, _, _]| [b]);
+    assert_eq!(drops.get(), 3);
+    assert_eq!(chosen.id, 1);
+
+    drop(chosen);
+    assert_eq!(drops.get(), 4);
+}
+
+#[test]
+fn failed_validation_drops_everything_once() {
+    let (values, drops) = DropCounter::many(LARGE_N);
+
+    let result = select_from(values).exact_with(1, |choices| choices);
+    assert!(result.is_err());
+    assert_eq!(drops.get(), LARGE_N);
+}
+
+#[test]
+fn zero_sized_values() {
+    let chosen = select_from(vec![(); LARGE_N]).with_indices(|mut choices| {
+        let last = choices.pop().unwrap();
+        [last, choices.swap_remove(0)]
+    });
+
+    assert_eq!(chosen, [(LARGE_N - 1, ()), (0, ())]);
+
+    let chosen = select_from_fixed([(); 3]).any_with(|choices| choices.into());
+    assert_eq!(chosen.len(), 3);
+}
+
+#[test]
+fn over_aligned_values() {
+    let pool: Vec<_> = (0..16).map(OverAligned).collect();
+
+    let chosen = select_from(pool).any_with(|choices| {
+        for choice in &choices {
+            let address = &**choice as *const OverAligned as usize;
+            assert!(address.is_multiple_of(64));
+        }
+        choices.into_iter().rev().step_by(5).collect()
+    });
+
+    assert_eq!(
+        chosen,
+        [
+            OverAligned(15),
+            OverAligned(10),
+            OverAligned(5),
+            OverAligned(0)
+        ]
+    );
+    assert!(chosen
+        .iter()
+        .all(|v| (v as *const _ as usize).is_multiple_of(64)));
+}
+
+#[test]
+fn panicking_chooser_drops_everything_once() {
+    let (values, drops) = DropCounter::many(6);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        select_from(values).with::<1, _>(|choices| {
+            drop(choices.into_iter().next());
+            panic!("chooser gave up");
+        })
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 6);
+}
+
+#[test]
+fn panic_during_drop_of_unchosen() {
+    let drops = Rc::new(Cell::new(0));
+    let values: Vec<_> = (0..5)
+        .map(|_| PanicOnNthDrop {
+            n: 2,
+            drops: Rc::clone(&drops),
+        })
+        .collect();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        select_from(values).any_with(|mut choices| {
+            choices.truncate(1);
+            choices
+        })
+    }));
+
+    // the second drop panicked, but every unchosen value still got dropped exactly once,
+    // and the chosen one was dropped while unwinding
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn partition_moves_every_value_once() {
+    let (values, drops) = DropCounter::many(LARGE_N);
+
+    let (chosen, rest) = select_from(values)
+        .partition_with(|choices| choices.into_iter().filter(|c| c.id % 3 == 0).collect());
+    // nothing is dropped, every value ends up on one side
+    assert_eq!(drops.get(), 0);
+    assert_eq!(chosen.len() + rest.len(), LARGE_N);
+    assert!(rest.iter().all(|v| v.id % 3 != 0));
+
+    drop((chosen, rest));
+    assert_eq!(drops.get(), LARGE_N);
+}
+
+#[test]
+fn panicking_partition_drops_everything_once() {
+    let (values, drops) = DropCounter::many(4);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        select_from(values).partition_with(|_| panic!("chooser gave up"))
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 4);
+}
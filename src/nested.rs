@@ -0,0 +1,113 @@
+//! Two-level selections, where the inner choices are only built for the chosen outer value.
+//! See [select_then](crate::select_then).
+
+use crate::choice::IndexedChoice;
+use crate::{select_from, Choice};
+
+/// Wraps outer choices paired with thunks producing their inner choices, see
+/// [select_then](crate::select_then).
+#[derive(Debug)]
+pub struct NestedSelector<A, F> {
+    choices: Vec<(A, F)>,
+}
+
+impl<A, B, F> NestedSelector<A, F>
+where
+    F: FnOnce() -> Vec<B>,
+{
+    pub(crate) fn with_choices(choices: Vec<(A, F)>) -> NestedSelector<A, F> {
+        NestedSelector { choices }
+    }
+
+    /// Uses `outer` to choose one of the outer values, then builds the inner choices of only
+    /// that value and uses `inner` to choose any number of them. Each selection runs in its own
+    /// guard scope, so choices can't be moved between them.
+    /// ```
+    /// use choose_from::select_then;
+    ///
+    /// let menu = vec![
+    ///     ("drinks", Box::new(|| vec!["tea", "coffee"]) as Box<dyn FnOnce() -> Vec<&'static str>>),
+    ///     ("snacks", Box::new(|| vec!["chips", "nuts", "cookies"])),
+    /// ];
+    ///
+    /// let (category, chosen) = select_then(menu).with(
+    ///     |mut categories| categories.pop().unwrap(),
+    ///     |snacks| snacks.into_iter().skip(1).collect(),
+    /// );
+    ///
+    /// assert_eq!(category, "snacks");
+    /// assert_eq!(chosen, ["nuts", "cookies"]);
+    /// ```
+    ///
+    /// An outer choice can't be carried over into the inner selection even when both levels
+    /// hold the same type, since the outer chooser is handed [IndexedChoice]s and the inner
+    /// one plain [Choice]s, and neither can be turned into the other. Any such attempt is
+    /// already a type mismatch before the separate guard scopes come into play, so there is
+    /// no compile_fail example here that would only show the guards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no outer choices, without calling either chooser.
+    pub fn with<O, I>(self, outer: O, inner: I) -> (A, Vec<B>)
+    where
+        O: FnOnce(Vec<IndexedChoice<'_, A>>) -> IndexedChoice<'_, A>,
+        I: FnOnce(Vec<Choice<'_, B>>) -> Vec<Choice<'_, B>>,
+    {
+        let (values, mut thunks): (Vec<A>, Vec<Option<F>>) = self
+            .choices
+            .into_iter()
+            .map(|(value, thunk)| (value, Some(thunk)))
+            .unzip();
+
+        assert!(
+            !values.is_empty(),
+            "cannot choose one value from zero choices"
+        );
+        let [(index, chosen)] = select_from(values).with_indices(|choices| [outer(choices)]);
+
+        // only the chosen value's inner choices are ever built
+        let thunk = thunks[index].take().expect("each thunk is only taken once");
+        let inner_chosen = select_from(thunk()).any_with(inner);
+
+        (chosen, inner_chosen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::select_then;
+    use std::cell::Cell;
+
+    #[test]
+    fn only_the_chosen_thunk_is_called() {
+        let calls = [Cell::new(0), Cell::new(0), Cell::new(0)];
+        let categories = calls
+            .iter()
+            .enumerate()
+            .map(|(i, calls)| {
+                (i, move || {
+                    calls.set(calls.get() + 1);
+                    vec![i * 10, i * 10 + 1]
+                })
+            })
+            .collect();
+
+        let (category, chosen) = select_then(categories).with(
+            |mut choices| choices.remove(1),
+            |mut choices| vec![choices.pop().unwrap()],
+        );
+
+        assert_eq!(category, 1);
+        assert_eq!(chosen, [11]);
+        assert_eq!(calls.map(|c| c.get()), [0, 1, 0]);
+    }
+
+    #[test]
+    fn inner_selection_can_be_empty() {
+        let (category, chosen) =
+            select_then(vec![("only", Vec::<u8>::new)]).with(|mut c| c.pop().unwrap(), |c| c);
+
+        assert_eq!(category, "only");
+        assert!(chosen.is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+//! Selectors that always have at least one choice, see
+//! [select_from_nonempty](crate::select_from_nonempty).
+
+use crate::postcondition::postcondition;
+use crate::{Choice, Guard, Selector};
+
+/// Wraps choices that are known to contain at least one value, so choosing one of
+/// them can't fail.
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct NonEmptySelector<T> {
+    // never empty
+    choices: Vec<T>,
+}
+
+impl<T> NonEmptySelector<T> {
+    pub(crate) fn with_choices(choices: Vec<T>) -> Option<NonEmptySelector<T>> {
+        if choices.is_empty() {
+            None
+        } else {
+            Some(NonEmptySelector { choices })
+        }
+    }
+
+    /// The number of choices, which is never zero.
+    // is_empty would always be false
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.choices.len()
+    }
+
+    /// The function `chooser` is used to choose exactly one of the choices. Unlike
+    /// [Selector::one_with] this can't panic, since there is always a choice to return.
+    /// ```
+    /// use choose_from::select_from_nonempty;
+    ///
+    /// let moves = select_from_nonempty(vec!["pass", "raise"]).unwrap();
+    ///
+    /// let chosen = moves.one_with(|mut choices| choices.pop().unwrap());
+    /// assert_eq!(chosen, "raise");
+    /// ```
+    pub fn one_with<C>(self, chooser: C) -> T
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> Choice<'_, T>,
+    {
+        let _guard = Guard;
+        let choices = Selector::with_choices(self.choices).into_choices(&_guard);
+        let total = choices.len();
+
+        let chosen = chooser(choices);
+        postcondition!(unique_choices([&chosen], total));
+
+        chosen.into_inner()
+    }
+
+    /// The same as [Selector::with]. The length isn't known at compile time, so a chooser
+    /// asked for more choices than there are can still only panic. Check [len](NonEmptySelector::len)
+    /// first if K may be larger.
+    /// ```
+    /// use choose_from::select_from_nonempty;
+    ///
+    /// let hand = select_from_nonempty(vec![3, 9, 1]).unwrap();
+    /// assert!(hand.len() >= 2);
+    ///
+    /// let [a, b] = hand.with(|mut choices| [choices.remove(0), choices.remove(0)]);
+    /// assert_eq!([a, b], [3, 9]);
+    /// ```
+    pub fn with<const K: usize, C>(self, chooser: C) -> [T; K]
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> [Choice<'_, T>; K],
+    {
+        Selector::with_choices(self.choices).with(chooser)
+    }
+
+    /// The same as [Selector::any_with].
+    pub fn any_with<C>(self, chooser: C) -> Vec<T>
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> Vec<Choice<'_, T>>,
+    {
+        Selector::with_choices(self.choices).any_with(chooser)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::EmptyChoices;
+    use crate::{select_from, select_from_nonempty};
+
+    #[test]
+    fn empty_input_is_an_error() {
+        assert_eq!(select_from_nonempty(Vec::<i32>::new()), Err(EmptyChoices));
+        assert_eq!(
+            select_from(0..0).try_into_nonempty().map(|s| s.len()),
+            Err(EmptyChoices)
+        );
+    }
+
+    #[test]
+    fn single_choice_is_picked_infallibly() {
+        let only = select_from(std::iter::once("only"))
+            .try_into_nonempty()
+            .unwrap();
+
+        assert_eq!(only.len(), 1);
+        assert_eq!(only.one_with(|mut c| c.remove(0)), "only");
+    }
+}
@@ -0,0 +1,55 @@
+//! Requiring choices that are truly owned, see
+//! [`Selector::assert_no_shared_handles`](crate::Selector::assert_no_shared_handles).
+//!
+//! The guard only stops a [Choice](crate::Choice) from escaping the chooser, not the value
+//! behind it. When the values are shared handles like `Rc<RefCell<Unit>>`, the chooser can
+//! clone the handle through the choice and keep it, and later read or change the chosen
+//! value after the selection is over:
+//! ```
+//! use std::cell::RefCell;
+//! use std::rc::Rc;
+//! use choose_from::select_from;
+//!
+//! let units = vec![Rc::new(RefCell::new(10)), Rc::new(RefCell::new(20))];
+//! let mut stash = Vec::new();
+//!
+//! let [chosen] = select_from(units).with(|mut choices| {
+//!     // the choice can't be kept, but a clone of its handle can
+//!     stash.push(Rc::clone(&choices[0]));
+//!     [choices.remove(0)]
+//! });
+//!
+//! *stash[0].borrow_mut() = 0;
+//! assert_eq!(*chosen.borrow(), 0);
+//! ```
+//! Selections where that matters can require [NotShared] values instead, which rejects
+//! shared handles at compile time. Owned values with interior mutability, like a
+//! `RefCell<Unit>` or a `Mutex<Unit>`, are still fine: the chooser only ever borrows them.
+
+use std::alloc::Allocator;
+use std::rc::{self, Rc};
+use std::sync::{self, Arc};
+
+/// Implemented for every type that doesn't contain a shared handle to its data, so that
+/// holding a value means being the only one holding it. Like [Send], this is implemented
+/// automatically, and opted out of by [Rc], [Arc], their weak pointers and shared
+/// references (and so by anything containing them). Raw pointers are left alone, since
+/// owning types like [Box] and [Vec] are built on them.
+/// ```compile_fail
+/// use std::rc::Rc;
+/// use choose_from::select_from;
+///
+/// let units = vec![Rc::new(1), Rc::new(2)];
+///
+/// select_from(units).assert_no_shared_handles();
+/// ```
+/// Custom handle types can opt out the same way, with a negative impl.
+// the pointers are generic over their allocator, and negative impls have to match the
+// type's parameters exactly, so every allocator is opted out
+pub auto trait NotShared {}
+
+impl<T: ?Sized, A: Allocator> !NotShared for Rc<T, A> {}
+impl<T: ?Sized, A: Allocator> !NotShared for rc::Weak<T, A> {}
+impl<T: ?Sized, A: Allocator> !NotShared for Arc<T, A> {}
+impl<T: ?Sized, A: Allocator> !NotShared for sync::Weak<T, A> {}
+impl<T: ?Sized> !NotShared for &T {}
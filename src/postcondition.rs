@@ -0,0 +1,111 @@
+// Internal post-condition checks for the selection terminals. The type system already
+// guarantees these (choices can't be duplicated or made up), so they're only compiled in
+// debug builds as a second line of defense, see the `postcondition!` macro.
+
+#[cfg(debug_assertions)]
+use crate::Choice;
+#[cfg(debug_assertions)]
+use std::collections::HashSet;
+
+/// Checks `$check` from this module, only in debug builds. The arguments aren't evaluated
+/// in release builds (the closure only keeps them from being reported as unused).
+macro_rules! postcondition {
+    ($check:ident($($arg:expr),* $(,)?)) => {
+        #[cfg(debug_assertions)]
+        $crate::postcondition::$check($($arg),*);
+        #[cfg(not(debug_assertions))]
+        let _ = || ($($arg),*);
+    };
+}
+
+pub(crate) use postcondition;
+
+/// Every chosen index is one of the `total` presented choices, and none are chosen twice.
+#[cfg(debug_assertions)]
+pub(crate) fn unique_indices<I>(indices: I, total: usize)
+where
+    I: IntoIterator<Item = usize>,
+{
+    let mut seen = HashSet::new();
+
+    for index in indices {
+        assert!(
+            index < total,
+            "post-condition failed: chose index {index} out of {total} choices"
+        );
+        assert!(
+            seen.insert(index),
+            "post-condition failed: chose index {index} twice"
+        );
+    }
+}
+
+/// Like [unique_indices], for choices that only know their index in debug builds.
+#[cfg(debug_assertions)]
+pub(crate) fn unique_choices<'c, 'guard: 'c, T: 'c, I>(choices: I, total: usize)
+where
+    I: IntoIterator<Item = &'c Choice<'guard, T>>,
+{
+    unique_indices(choices.into_iter().map(Choice::index), total);
+}
+
+/// A partition accounts for every presented choice exactly once.
+#[cfg(debug_assertions)]
+pub(crate) fn partition(chosen: usize, rest: usize, total: usize) {
+    assert_eq!(
+        chosen + rest,
+        total,
+        "post-condition failed: {chosen} chosen and {rest} remaining values out of {total} choices"
+    );
+}
+
+#[cfg(all(test, debug_assertions))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_valid_selections() {
+        unique_indices([3, 0, 2], 4);
+        unique_indices([], 0);
+        partition(1, 3, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "chose index 4 out of 4 choices")]
+    fn out_of_range_index() {
+        unique_indices([0, 4], 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "chose index 1 twice")]
+    fn duplicated_index() {
+        unique_indices([1, 2, 1], 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "2 chosen and 3 remaining values out of 4 choices")]
+    fn partition_with_extra_values() {
+        partition(2, 3, 4);
+    }
+
+    // choices can't be duplicated outside the crate, so the duplicate is forged here
+    #[test]
+    #[should_panic(expected = "chose index 2 twice")]
+    fn duplicated_choice() {
+        let guard = crate::Guard;
+        let forged = [
+            Choice::with_guard('a', 2, &guard),
+            Choice::with_guard('b', 0, &guard),
+            Choice::with_guard('a', 2, &guard),
+        ];
+
+        unique_choices(&forged, 3);
+    }
+
+    // the terminals run their checks through the macro
+    #[test]
+    #[should_panic(expected = "chose index 0 twice")]
+    fn macro_runs_checks() {
+        postcondition!(unique_indices([0, 0], 1));
+    }
+}
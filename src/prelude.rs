@@ -0,0 +1,83 @@
+//! Extension traits for building selectors at the end of a chain, instead of wrapping it
+//! in [select_from](crate::select_from) or [select_from_fixed](crate::select_from_fixed).
+//! ```
+//! use choose_from::prelude::*;
+//!
+//! let chosen = (1..=10)
+//!     .filter(|n| n % 3 == 0)
+//!     .select_from()
+//!     .any_with(|choices| choices.into_iter().filter(|c| **c > 3).collect());
+//!
+//! assert_eq!(chosen, [6, 9]);
+//! ```
+
+pub use crate::choice::IndexedChoice;
+pub use crate::Choice;
+
+use crate::{Selector, SelectorFixed};
+
+/// Adds [select_from](SelectFromIteratorExt::select_from) to everything that can be
+/// iterated over.
+pub trait SelectFromIteratorExt: IntoIterator + Sized {
+    /// Same as [select_from](crate::select_from), with the choices as the receiver.
+    /// ```
+    /// use choose_from::prelude::*;
+    ///
+    /// let words = ["apple", "bob", "kayak", "pear"];
+    ///
+    /// let [first] = words
+    ///     .into_iter()
+    ///     .filter(|w| w.chars().eq(w.chars().rev()))
+    ///     .select_from()
+    ///     .with(|mut choices| [choices.remove(0)]);
+    ///
+    /// assert_eq!(first, "bob");
+    /// ```
+    fn select_from(self) -> Selector<Self, Self::Item> {
+        Selector::with_choices(self)
+    }
+}
+
+impl<I: IntoIterator> SelectFromIteratorExt for I {}
+
+/// Adds [select_from_fixed](SelectFromArrayExt::select_from_fixed) to arrays.
+pub trait SelectFromArrayExt<const N: usize, T> {
+    /// Same as [select_from_fixed](crate::select_from_fixed), with the choices as the
+    /// receiver.
+    /// ```
+    /// use choose_from::prelude::*;
+    ///
+    /// let chosen = ["rock", "paper", "scissors"]
+    ///     .select_from_fixed()
+    ///     .with(|[_, paper, _]| [paper]);
+    ///
+    /// assert_eq!(chosen, ["paper"]);
+    /// ```
+    fn select_from_fixed(self) -> SelectorFixed<N, T>;
+}
+
+impl<const N: usize, T> SelectFromArrayExt<N, T> for [T; N] {
+    fn select_from_fixed(self) -> SelectorFixed<N, T> {
+        SelectorFixed::with_choices(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn postfix_forms_match_the_functions() {
+        fn last(mut choices: Vec<Choice<'_, i32>>) -> [Choice<'_, i32>; 1] {
+            [choices.pop().unwrap()]
+        }
+        assert_eq!(
+            vec![1, 2, 3].select_from().with(last),
+            crate::select_from(vec![1, 2, 3]).with(last)
+        );
+
+        // arrays are iterable too, so both forms are available on them
+        assert_eq!([4, 5].select_from().with(|mut c| [c.remove(0)]), [4]);
+        assert_eq!([4, 5].select_from_fixed().with(|[_, b]| [b]), [5]);
+    }
+}
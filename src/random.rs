@@ -0,0 +1,367 @@
+// Random choosers, behind the `rand` feature. They run through the regular selection
+// methods, so a random selection has the same guarantees as any other.
+
+use crate::error::{ChooseError, WrongSelectionCount};
+use crate::{Choice, Selector, SelectorFixed};
+use rand::seq::index;
+use rand::Rng;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+impl<I, T> Selector<I, T>
+where
+    I: IntoIterator<Item = T>,
+{
+    /// Chooses K distinct choices uniformly at random, in random order. If there are fewer
+    /// than K choices a [WrongCount](crate::error::ChooseErrorKind::WrongCount) error is
+    /// returned, with the number of choices there were. Only available with the `rand`
+    /// feature.
+    /// ```
+    /// use choose_from::select_from;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(7);
+    ///
+    /// let [a, b] = select_from(1..=10).choose_random(&mut rng).unwrap();
+    /// assert_ne!(a, b);
+    ///
+    /// let too_many = select_from(1..=2).choose_random::<3, _>(&mut rng);
+    /// assert_eq!(too_many.unwrap_err().as_wrong_count().unwrap().actual, 2);
+    /// ```
+    pub fn choose_random<const K: usize, R>(self, rng: &mut R) -> Result<[T; K], ChooseError>
+    where
+        R: Rng + ?Sized,
+    {
+        let chosen = self.choose_random_k(K, rng)?;
+
+        match chosen.try_into() {
+            Ok(chosen) => Ok(chosen),
+            Err(_) => unreachable!("exactly {} values were chosen", K),
+        }
+    }
+
+    /// Like [choose_random](Selector::choose_random), but for a number of choices only known
+    /// at runtime.
+    /// ```
+    /// use choose_from::select_from;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(7);
+    /// let hand = select_from(1..=52).choose_random_k(5, &mut rng).unwrap();
+    ///
+    /// assert_eq!(hand.len(), 5);
+    /// ```
+    pub fn choose_random_k<R>(self, k: usize, rng: &mut R) -> Result<Vec<T>, ChooseError>
+    where
+        R: Rng + ?Sized,
+    {
+        let mut available = None;
+
+        let chosen = self.any_with(|choices| {
+            if k > choices.len() {
+                available = Some(choices.len());
+                return Vec::new();
+            }
+            sample(choices, k, rng)
+        });
+
+        match available {
+            Some(actual) => Err(WrongSelectionCount {
+                expected: k,
+                actual,
+            }
+            .into()),
+            None => Ok(chosen),
+        }
+    }
+
+    /// Chooses K distinct choices at random, split between strata in proportion to their
+    /// sizes. Each choice belongs to the stratum named by `label`, and every stratum gets
+    /// `K * size / total` choices rounded down, with the choices left over going to the
+    /// strata with the largest remainders (the earlier stratum on a tie). Within a stratum
+    /// the choices are sampled without replacement.
+    ///
+    /// The chosen values are grouped by stratum, in the order each stratum first appears,
+    /// and are in random order within a stratum. If there are fewer than K choices, or a
+    /// stratum has fewer choices than its share, a
+    /// [WrongCount](crate::error::ChooseErrorKind::WrongCount) error is returned. Only
+    /// available with the `rand` feature.
+    /// ```
+    /// use choose_from::select_from;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(7);
+    ///
+    /// // (question, topic), and topics should show up as often as in the full questionnaire
+    /// let questions = vec![
+    ///     (1, "combat"), (2, "combat"), (3, "story"), (4, "combat"), (5, "story"),
+    ///     (6, "combat"), (7, "music"), (8, "combat"), (9, "story"), (10, "music"),
+    /// ];
+    ///
+    /// let picked: [_; 4] = select_from(questions)
+    ///     .sample_stratified(|q| q.1, &mut rng)
+    ///     .unwrap();
+    /// let topics = picked.map(|q| q.1);
+    ///
+    /// // the shares are 2, 1.2 and 0.8, so the spare choice goes to music
+    /// assert_eq!(topics, ["combat", "combat", "story", "music"]);
+    /// ```
+    pub fn sample_stratified<const K: usize, L, F, R>(
+        self,
+        label: F,
+        rng: &mut R,
+    ) -> Result<[T; K], ChooseError>
+    where
+        L: Eq + Hash,
+        F: Fn(&T) -> L,
+        R: Rng + ?Sized,
+    {
+        let mut error = None;
+
+        let chosen = self.any_with(|choices| {
+            if K > choices.len() {
+                error = Some(WrongSelectionCount {
+                    expected: K,
+                    actual: choices.len(),
+                });
+                return Vec::new();
+            }
+
+            // strata in the order they first appear
+            let mut strata: Vec<Vec<Choice<'_, T>>> = Vec::new();
+            let mut positions = HashMap::new();
+            for choice in choices {
+                let stratum = *positions.entry(label(&choice)).or_insert_with(|| {
+                    strata.push(Vec::new());
+                    strata.len() - 1
+                });
+                strata[stratum].push(choice);
+            }
+
+            let sizes: Vec<_> = strata.iter().map(Vec::len).collect();
+            let mut chosen = Vec::with_capacity(K);
+            for (stratum, quota) in strata.into_iter().zip(quotas(&sizes, K)) {
+                // rounding up only happens for a share short of the whole stratum, so this
+                // holds whenever K fits, but a stratum must never be asked for more than it has
+                if quota > stratum.len() {
+                    error = Some(WrongSelectionCount {
+                        expected: quota,
+                        actual: stratum.len(),
+                    });
+                    return Vec::new();
+                }
+                chosen.extend(sample(stratum, quota, rng));
+            }
+
+            chosen
+        });
+
+        if let Some(error) = error {
+            return Err(error.into());
+        }
+
+        match chosen.try_into() {
+            Ok(chosen) => Ok(chosen),
+            Err(_) => unreachable!("the quotas add up to {}", K),
+        }
+    }
+}
+
+impl<const N: usize, T> SelectorFixed<N, T> {
+    /// Chooses K distinct choices uniformly at random, in random order, see
+    /// [Selector::choose_random]. Choosing more than N choices is a compile time error.
+    /// Only available with the `rand` feature.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(7);
+    /// let [coin] = select_from_fixed(["heads", "tails"]).choose_random(&mut rng);
+    ///
+    /// assert!(coin == "heads" || coin == "tails");
+    /// ```
+    pub fn choose_random<const K: usize, R>(self, rng: &mut R) -> [T; K]
+    where
+        R: Rng + ?Sized,
+    {
+        self.with(|choices| match sample(choices.into(), K, rng).try_into() {
+            Ok(chosen) => chosen,
+            Err(_) => unreachable!("exactly {} choices were sampled", K),
+        })
+    }
+}
+
+// takes k distinct choices out of `choices`, in random order
+fn sample<'guard, T, R>(
+    choices: Vec<Choice<'guard, T>>,
+    k: usize,
+    rng: &mut R,
+) -> Vec<Choice<'guard, T>>
+where
+    R: Rng + ?Sized,
+{
+    let mut slots: Vec<_> = choices.into_iter().map(Some).collect();
+
+    index::sample(rng, slots.len(), k)
+        .into_iter()
+        .map(|i| slots[i].take().expect("sampled indices are distinct"))
+        .collect()
+}
+
+// splits k between strata of the given sizes in proportion to them, by largest remainder
+fn quotas(sizes: &[usize], k: usize) -> Vec<usize> {
+    let total: usize = sizes.iter().sum();
+    if total == 0 {
+        return vec![0; sizes.len()];
+    }
+
+    let mut quotas: Vec<_> = sizes.iter().map(|size| k * size / total).collect();
+    let short = k - quotas.iter().sum::<usize>();
+
+    // a stable sort, so ties go to the earlier stratum
+    let mut by_remainder: Vec<_> = (0..sizes.len()).collect();
+    by_remainder.sort_by_key(|&i| std::cmp::Reverse(k * sizes[i] % total));
+    for &i in &by_remainder[..short] {
+        quotas[i] += 1;
+    }
+
+    quotas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::quotas;
+    use crate::error::{ChooseErrorKind, WrongSelectionCount};
+    use crate::{select_from, select_from_fixed};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn seeded_selections_are_pinned() {
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let chosen: [i32; 3] = select_from(0..10).choose_random(&mut rng).unwrap();
+        assert_eq!(chosen, [1, 2, 5]);
+
+        let chosen = select_from(0..10).choose_random_k(4, &mut rng).unwrap();
+        assert_eq!(chosen, [9, 6, 5, 0]);
+
+        let chosen: [char; 2] = select_from_fixed(['a', 'b', 'c', 'd']).choose_random(&mut rng);
+        assert_eq!(chosen, ['d', 'b']);
+    }
+
+    #[test]
+    fn all_choices_in_random_order() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let mut chosen = select_from(0..20).choose_random_k(20, &mut rng).unwrap();
+        chosen.sort();
+
+        assert_eq!(chosen, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn too_many_is_an_error() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let err = select_from(vec![1, 2])
+            .choose_random_k(3, &mut rng)
+            .unwrap_err();
+        assert_eq!(err.kind(), ChooseErrorKind::WrongCount);
+        assert_eq!(
+            err.as_wrong_count(),
+            Some(&WrongSelectionCount {
+                expected: 3,
+                actual: 2
+            })
+        );
+
+        assert!(select_from(Vec::<i32>::new())
+            .choose_random_k(0, &mut rng)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn largest_remainders_get_the_spare_choices() {
+        // exact shares are 3, 1.5 and 0.5, the tie goes to the earlier stratum
+        assert_eq!(quotas(&[6, 3, 1], 5), [3, 2, 0]);
+        // exact shares are 1.2, 1.2 and 1.6
+        assert_eq!(quotas(&[3, 3, 4], 4), [1, 1, 2]);
+        assert_eq!(quotas(&[4, 2], 6), [4, 2]);
+        assert_eq!(quotas(&[4, 2], 0), [0, 0]);
+        assert!(quotas(&[], 0).is_empty());
+    }
+
+    #[test]
+    fn stratified_counts_follow_stratum_sizes() {
+        let mut rng = StdRng::seed_from_u64(3);
+
+        // 60 'a', 30 'b' and 10 'c', interleaved
+        let population: Vec<(usize, char)> = (0..100)
+            .map(|i| match i % 10 {
+                0..=5 => (i, 'a'),
+                6..=8 => (i, 'b'),
+                _ => (i, 'c'),
+            })
+            .collect();
+
+        for _ in 0..20 {
+            let chosen: [_; 10] = select_from(population.clone())
+                .sample_stratified(|v| v.1, &mut rng)
+                .unwrap();
+
+            let labels = chosen.map(|v| v.1);
+            assert_eq!(labels, ['a', 'a', 'a', 'a', 'a', 'a', 'b', 'b', 'b', 'c']);
+
+            let mut values = chosen.map(|v| v.0);
+            values.sort();
+            assert!(values.windows(2).all(|w| w[0] < w[1]));
+        }
+    }
+
+    #[test]
+    fn seeded_stratified_selections_are_pinned() {
+        let chosen = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            select_from(0..12)
+                .sample_stratified::<4, _, _, _>(|n| n % 3, &mut rng)
+                .unwrap()
+        };
+
+        assert_eq!(chosen(42), [0, 6, 7, 11]);
+        assert_eq!(chosen(42), chosen(42));
+    }
+
+    #[test]
+    fn stratified_errors() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let err = select_from(vec![1, 2, 3])
+            .sample_stratified::<4, _, _, _>(|n| n % 2, &mut rng)
+            .unwrap_err();
+        assert_eq!(
+            err.as_wrong_count(),
+            Some(&WrongSelectionCount {
+                expected: 4,
+                actual: 3
+            })
+        );
+
+        // every stratum is used up exactly, which is fine
+        let mut all = select_from(vec![1, 2, 3])
+            .sample_stratified::<3, _, _, _>(|n| n % 2, &mut rng)
+            .unwrap();
+        all[..2].sort();
+        assert_eq!(all, [1, 3, 2]);
+
+        let none: [i32; 0] = select_from(Vec::new())
+            .sample_stratified(|n: &i32| *n, &mut rng)
+            .unwrap();
+        assert_eq!(none, []);
+    }
+}
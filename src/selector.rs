@@ -1,4 +1,25 @@
+use crate::choice::{ChoiceSet, IndexedChoice, LazyChoices, Slots};
+use crate::draft::Draft;
+use crate::error::{
+    ChooseError, Declined, DuplicateKey, DuplicateSelection, EmptyChoices, RetriesExhausted,
+    SelectionCountError, UnrelatedPair, WrongSelectionCount,
+};
+use crate::frozen::FrozenSelector;
+use crate::limits::{LimitedChoice, LimitedSelector};
+use crate::mapped::MappedSelector;
+use crate::nonempty::NonEmptySelector;
+use crate::postcondition::postcondition;
+#[cfg(feature = "rand")]
+use crate::shuffled::ShuffledSelector;
+use crate::swipe::{Swipe, SwipeState};
+use crate::zip::ZipSelector;
 use crate::{choice, Choice, Guard};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::iter::{Chain, Filter, Rev, Skip, Take};
+use std::ops::{ControlFlow, RangeBounds};
 
 /// Wraps a variable amount of choices and provides methods that guarantee selection from those choices.
 #[derive(Debug, Hash, PartialEq, Eq)]
@@ -9,6 +30,16 @@ where
     choices: I,
 }
 
+// derived Clone would also require T: Clone, but only the choices are cloned
+impl<I, T> Clone for Selector<I, T>
+where
+    I: IntoIterator<Item = T> + Clone,
+{
+    fn clone(&self) -> Self {
+        Selector::with_choices(self.choices.clone())
+    }
+}
+
 impl<I, T> Selector<I, T>
 where
     I: IntoIterator<Item = T>,
@@ -55,12 +86,508 @@ where
         let _guard = Guard;
 
         let choices = self.into_choices(&_guard);
+        let total = choices.len();
+
+        let chosen = chooser(choices);
+        postcondition!(unique_choices(&chosen, total));
 
-        chooser(choices).map(Choice::into_inner)
+        chosen.map(Choice::into_inner)
         // _guard is dropped when function returns, which means that no one
         // has any Choice values anymore
     }
 
+    /// Like [with](Selector::with), but checks that there are at least K choices before
+    /// calling `chooser`. If there are fewer, a [WrongCount](crate::error::ChooseErrorKind::WrongCount)
+    /// error with the number of choices there were is returned instead, so a chooser never
+    /// has to make up for choices that aren't there.
+    /// ```
+    /// use choose_from::select_from;
+    /// use choose_from::error::WrongSelectionCount;
+    ///
+    /// let pair = select_from(vec!["ann", "bob"]).checked_with(|mut c| [c.remove(1), c.remove(0)]);
+    /// assert_eq!(pair.unwrap(), ["bob", "ann"]);
+    ///
+    /// let trio = select_from(vec!["ann", "bob"]).checked_with::<3, _>(|_| unreachable!());
+    /// assert_eq!(trio.unwrap_err().as_wrong_count(), Some(&WrongSelectionCount { expected: 3, actual: 2 }));
+    /// ```
+    pub fn checked_with<const K: usize, C>(self, chooser: C) -> Result<[T; K], ChooseError>
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> [Choice<'_, T>; K],
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+        let total = choices.len();
+
+        if total < K {
+            return Err(WrongSelectionCount {
+                expected: K,
+                actual: total,
+            }
+            .into());
+        }
+
+        let chosen = chooser(choices);
+        postcondition!(unique_choices(&chosen, total));
+
+        Ok(chosen.map(Choice::into_inner))
+    }
+
+    /// Like [with](Selector::with), but `ctx` is handed to the chooser along with the choices,
+    /// and handed back out once it returns. Useful for context the chooser needs by value or
+    /// mutably (an rng, a log) that the caller wants to keep using after the selection.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// // a tiny xorshift generator, standing in for something like `StdRng`
+    /// struct Rng(u64);
+    ///
+    /// impl Rng {
+    ///     fn below(&mut self, n: usize) -> usize {
+    ///         self.0 ^= self.0 << 13;
+    ///         self.0 ^= self.0 >> 7;
+    ///         self.0 ^= self.0 << 17;
+    ///         (self.0 % n as u64) as usize
+    ///     }
+    /// }
+    ///
+    /// let mut rng = Rng(0x2545_f491);
+    ///
+    /// for round in 0..3 {
+    ///     let ([loot], used) = select_from(vec!["gold", "gem", "scroll"]).with_context(
+    ///         rng,
+    ///         |mut rng, mut choices| {
+    ///             let pick = rng.below(choices.len());
+    ///             ([choices.remove(pick)], rng)
+    ///         },
+    ///     );
+    ///     rng = used;
+    ///
+    ///     assert!(["gold", "gem", "scroll"].contains(&loot));
+    /// }
+    ///
+    /// // the generator kept its state across every selection
+    /// assert_ne!(rng.0, 0x2545_f491);
+    /// ```
+    pub fn with_context<Ctx, const K: usize, C>(self, ctx: Ctx, chooser: C) -> ([T; K], Ctx)
+    where
+        C: FnOnce(Ctx, Vec<Choice<'_, T>>) -> ([Choice<'_, T>; K], Ctx),
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+        let total = choices.len();
+
+        let (chosen, ctx) = chooser(ctx, choices);
+        postcondition!(unique_choices(&chosen, total));
+
+        (chosen.map(Choice::into_inner), ctx)
+    }
+
+    /// Like [with](Selector::with), but each chosen value is returned together with its
+    /// position among the choices, no matter how the chooser rearranged them. After an
+    /// adapter like [filter_choices](Selector::filter_choices) the positions count the
+    /// adapted choices, see [IndexedChoice::index](crate::choice::IndexedChoice::index).
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let deck = vec!["ace", "king", "queen", "jack"];
+    ///
+    /// let chosen = select_from(deck).with_indices(|mut choices| {
+    ///     choices.reverse();
+    ///     [choices.remove(0), choices.remove(1)]
+    /// });
+    ///
+    /// assert_eq!(chosen, [(3, "jack"), (1, "king")]);
+    /// ```
+    pub fn with_indices<const K: usize, C>(self, chooser: C) -> [(usize, T); K]
+    where
+        C: FnOnce(Vec<IndexedChoice<'_, T>>) -> [IndexedChoice<'_, T>; K],
+    {
+        let mut slots = Slots::new(self.choices);
+        let total = slots.len();
+        let chosen = chooser(slots.choices()).map(|c| c.index());
+        postcondition!(unique_indices(chosen, total));
+
+        chosen.map(|i| (i, slots.take(i)))
+    }
+
+    /// Like [with](Selector::with), but the chooser returns `K` pairs of choices. Since every
+    /// choice can only be returned once, the pairs are always disjoint.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let drawer = vec!["red", "blue", "red", "blue"];
+    ///
+    /// let socks = select_from(drawer).with_pairs(|choices| {
+    ///     let [r1, b1, r2, b2]: [_; 4] = choices.try_into().unwrap();
+    ///     [(b1, b2), (r1, r2)]
+    /// });
+    ///
+    /// assert_eq!(socks, [("blue", "blue"), ("red", "red")]);
+    /// ```
+    pub fn with_pairs<const K: usize, C>(self, chooser: C) -> [(T, T); K]
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> [(Choice<'_, T>, Choice<'_, T>); K],
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+        let total = choices.len();
+
+        let chosen = chooser(choices);
+        postcondition!(unique_choices(
+            chosen.iter().flat_map(|(a, b)| [a, b]),
+            total
+        ));
+
+        chosen.map(|(a, b)| (a.into_inner(), b.into_inner()))
+    }
+
+    /// Like [with_pairs](Selector::with_pairs), but for returning any number of pairs.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let pairs = select_from(1..=5).any_with_pairs(|choices| {
+    ///     let mut choices = choices.into_iter();
+    ///     let mut pairs = Vec::new();
+    ///     while let (Some(a), Some(b)) = (choices.next(), choices.next()) {
+    ///         pairs.push((a, b));
+    ///     }
+    ///     pairs
+    /// });
+    ///
+    /// assert_eq!(pairs, [(1, 2), (3, 4)]);
+    /// ```
+    pub fn any_with_pairs<C>(self, chooser: C) -> Vec<(T, T)>
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> Vec<(Choice<'_, T>, Choice<'_, T>)>,
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+        let total = choices.len();
+
+        let chosen = chooser(choices);
+        postcondition!(unique_choices(
+            chosen.iter().flat_map(|(a, b)| [a, b]),
+            total
+        ));
+
+        chosen
+            .into_iter()
+            .map(|(a, b)| (a.into_inner(), b.into_inner()))
+            .collect()
+    }
+
+    /// Like [any_with_pairs](Selector::any_with_pairs), but both members of every pair must
+    /// satisfy `related`, otherwise the position of the first unrelated pair is returned.
+    /// ```
+    /// use choose_from::select_from;
+    /// use choose_from::error::UnrelatedPair;
+    ///
+    /// let drawer = vec!["red", "blue", "red", "green"];
+    ///
+    /// let socks = select_from(drawer).any_with_pairs_related_by(
+    ///     |a, b| a == b,
+    ///     |choices| {
+    ///         let [r1, b, r2, g]: [_; 4] = choices.try_into().unwrap();
+    ///         vec![(r1, r2), (b, g)]
+    ///     },
+    /// );
+    ///
+    /// assert_eq!(socks.unwrap_err().as_unrelated_pair(), Some(&UnrelatedPair { pair: 1 }));
+    /// ```
+    pub fn any_with_pairs_related_by<R, C>(
+        self,
+        related: R,
+        chooser: C,
+    ) -> Result<Vec<(T, T)>, ChooseError>
+    where
+        R: Fn(&T, &T) -> bool,
+        C: FnOnce(Vec<Choice<'_, T>>) -> Vec<(Choice<'_, T>, Choice<'_, T>)>,
+    {
+        let pairs = self.any_with_pairs(chooser);
+
+        match pairs.iter().position(|(a, b)| !related(a, b)) {
+            Some(pair) => Err(UnrelatedPair { pair }.into()),
+            None => Ok(pairs),
+        }
+    }
+
+    /// Like [with](Selector::with), but for choosers that can fail. An error from `chooser` is
+    /// returned as is, and none of the choices are returned with it.
+    /// ```
+    /// use std::io;
+    /// use choose_from::select_from;
+    ///
+    /// let chosen = select_from(vec![1, 2, 3]).try_with(|_| {
+    ///     // imagine asking a remote UI for a selection here
+    ///     Err::<[_; 2], _>(io::Error::new(io::ErrorKind::TimedOut, "no answer"))
+    /// });
+    ///
+    /// assert_eq!(chosen.unwrap_err().kind(), io::ErrorKind::TimedOut);
+    /// ```
+    pub fn try_with<const K: usize, C, E>(self, chooser: C) -> Result<[T; K], E>
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> Result<[Choice<'_, T>; K], E>,
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+        let total = choices.len();
+
+        let chosen = chooser(choices)?;
+        postcondition!(unique_choices(&chosen, total));
+
+        Ok(chosen.map(Choice::into_inner))
+    }
+
+    /// Like [try_with](Selector::try_with), but the chooser reports why it declined to make
+    /// a selection with a [Declined], so the reason isn't lost like it would be with
+    /// [opt_with](Selector::opt_with).
+    /// ```
+    /// use choose_from::select_from;
+    /// use choose_from::error::Declined;
+    ///
+    /// let enemies = vec![("slime", 3), ("bat", 5)];
+    ///
+    /// let target = select_from(enemies).with_declinable(|choices| {
+    ///     match choices.into_iter().find(|c| c.1 > 10) {
+    ///         Some(boss) => Ok([boss]),
+    ///         None => Err(Declined::NoAcceptableOption {
+    ///             reason: String::from("no enemy is worth the mana"),
+    ///         }),
+    ///     }
+    /// });
+    ///
+    /// assert!(matches!(target, Err(Declined::NoAcceptableOption { .. })));
+    /// ```
+    pub fn with_declinable<const K: usize, C>(self, chooser: C) -> Result<[T; K], Declined>
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> Result<[Choice<'_, T>; K], Declined>,
+    {
+        self.try_with(chooser)
+    }
+
+    /// Like [any_with](Selector::any_with), but for choosers that can fail. An error from
+    /// `chooser` is returned as is, and none of the choices are returned with it.
+    /// ```
+    /// use std::io;
+    /// use choose_from::select_from;
+    ///
+    /// fn ask(_options: &[i32]) -> io::Result<Vec<usize>> {
+    ///     Err(io::Error::new(io::ErrorKind::BrokenPipe, "ui went away"))
+    /// }
+    ///
+    /// let chosen: io::Result<Vec<i32>> = select_from(vec![1, 2, 3]).try_any_with(|choices| {
+    ///     let values: Vec<i32> = choices.iter().map(|c| **c).collect();
+    ///     let picks = ask(&values)?;
+    ///
+    ///     Ok(choices
+    ///         .into_iter()
+    ///         .enumerate()
+    ///         .filter(|(i, _)| picks.contains(i))
+    ///         .map(|(_, c)| c)
+    ///         .collect())
+    /// });
+    ///
+    /// assert_eq!(chosen.unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+    /// ```
+    pub fn try_any_with<C, E>(self, chooser: C) -> Result<Vec<T>, E>
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> Result<Vec<Choice<'_, T>>, E>,
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+        let total = choices.len();
+
+        let chosen = chooser(choices)?;
+        postcondition!(unique_choices(&chosen, total));
+
+        Ok(choice::to_values(chosen))
+    }
+
+    /// Like [try_any_with](Selector::try_any_with), but a rejected selection is retried: the
+    /// same choices are presented again, in the same order, until `chooser` returns `Ok` or
+    /// `max_attempts` attempts have been rejected. The error then carries the reason of the
+    /// last rejection.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// // imagine these are typed in by a player
+    /// let mut inputs = ["7", "x", "1"].into_iter();
+    ///
+    /// let chosen = select_from(vec!["north", "south"]).with_retries(3, |mut choices| {
+    ///     let input = inputs.next().unwrap();
+    ///     let index: usize = input.parse().map_err(|_| format!("{input} is not a number"))?;
+    ///
+    ///     if index < choices.len() {
+    ///         Ok(vec![choices.remove(index)])
+    ///     } else {
+    ///         Err(format!("there is no exit {index}"))
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(chosen.unwrap(), ["south"]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_attempts` is zero, since there would be no rejection to report.
+    pub fn with_retries<C, E>(
+        self,
+        max_attempts: usize,
+        mut chooser: C,
+    ) -> Result<Vec<T>, RetriesExhausted<E>>
+    where
+        I: Clone,
+        C: FnMut(Vec<Choice<'_, T>>) -> Result<Vec<Choice<'_, T>>, E>,
+    {
+        assert!(max_attempts > 0, "with_retries needs at least one attempt");
+
+        for _ in 1..max_attempts {
+            if let Ok(chosen) = self.clone().try_any_with(&mut chooser) {
+                return Ok(chosen);
+            }
+        }
+
+        // the last attempt can use the choices themselves
+        self.try_any_with(chooser)
+            .map_err(|last_reason| RetriesExhausted {
+                attempts: max_attempts,
+                last_reason,
+            })
+    }
+
+    /// Like [with](Selector::with), but `chooser` may cancel the selection by returning `None`,
+    /// in which case all the choices are dropped.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// // imagine this is read from a prompt
+    /// let input = "q";
+    ///
+    /// let chosen = select_from(vec!["rock", "paper", "scissors"]).opt_with(|choices| {
+    ///     let index: usize = match input {
+    ///         "q" => return None,
+    ///         n => n.parse().ok()?,
+    ///     };
+    ///
+    ///     choices.into_iter().nth(index).map(|c| [c])
+    /// });
+    ///
+    /// assert_eq!(chosen, None);
+    /// ```
+    pub fn opt_with<const K: usize, C>(self, chooser: C) -> Option<[T; K]>
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> Option<[Choice<'_, T>; K]>,
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+        let total = choices.len();
+
+        let chosen = chooser(choices)?;
+        postcondition!(unique_choices(&chosen, total));
+
+        Some(chosen.map(Choice::into_inner))
+    }
+
+    /// Like [with](Selector::with), but for choosing exactly one value, which is returned directly.
+    /// ```
+    /// use choose_from::{select_from, Choice};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq)]
+    /// enum Suit {
+    ///     Clubs,
+    ///     Diamonds,
+    ///     Hearts,
+    ///     Spades,
+    /// }
+    ///
+    /// struct Suits(Vec<Suit>);
+    ///
+    /// impl Suits {
+    ///     // where chooser is some external function that chooses from the provided suits
+    ///     pub fn choose_suit<C>(&self, chooser: C) -> Suit
+    ///     where
+    ///         C: FnOnce(Vec<Choice<'_, Suit>>) -> Choice<'_, Suit>,
+    ///     {
+    ///         // this suit is guaranteed to be from our choices
+    ///         select_from(self.0.clone()).one_with(chooser)
+    ///     }
+    /// }
+    ///
+    /// let suits = Suits(vec![Suit::Clubs, Suit::Hearts]);
+    /// let suit = suits.choose_suit(|mut choices| choices.pop().unwrap());
+    ///
+    /// assert_eq!(suit, Suit::Hearts);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no choices, without calling `chooser`. See
+    /// [checked_one_with](Selector::checked_one_with) for a non-panicking version.
+    pub fn one_with<C>(self, chooser: C) -> T
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> Choice<'_, T>,
+    {
+        self.checked_one_with(chooser)
+            .expect("cannot choose one value from zero choices")
+    }
+
+    /// Like [one_with](Selector::one_with), but returns `None` without calling `chooser`
+    /// when there are no choices.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let chosen = select_from(Vec::<i32>::new()).checked_one_with(|_| unreachable!());
+    ///
+    /// assert_eq!(chosen, None);
+    /// ```
+    pub fn checked_one_with<C>(self, chooser: C) -> Option<T>
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> Choice<'_, T>,
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+        let total = choices.len();
+
+        if choices.is_empty() {
+            return None;
+        }
+
+        let chosen = chooser(choices);
+        postcondition!(unique_choices([&chosen], total));
+
+        Some(chosen.into_inner())
+    }
+
+    /// Like [checked_one_with](Selector::checked_one_with), but if there is exactly one
+    /// choice it is returned right away without calling `chooser`. Useful for skipping a
+    /// prompt that has only one answer.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let only = select_from(vec!["continue"]).auto_or_with(|_| unreachable!());
+    /// assert_eq!(only, Some("continue"));
+    ///
+    /// let picked = select_from(vec!["yes", "no"]).auto_or_with(|mut c| c.pop().unwrap());
+    /// assert_eq!(picked, Some("no"));
+    /// ```
+    pub fn auto_or_with<C>(self, chooser: C) -> Option<T>
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> Choice<'_, T>,
+    {
+        let _guard = Guard;
+        let mut choices = self.into_choices(&_guard);
+        let total = choices.len();
+
+        let chosen = match total {
+            0 => return None,
+            1 => choices.pop()?,
+            _ => chooser(choices),
+        };
+        postcondition!(unique_choices([&chosen], total));
+
+        Some(chosen.into_inner())
+    }
+
     /// Like [with](Selector::with), but for returning any number of chosen values. Use this when
     /// you want to ensure some values come from the choices, but the amount of chosen values returned
     /// doesn't matter.
@@ -81,17 +608,1918 @@ where
     {
         let _guard = Guard;
         let choices = self.into_choices(&_guard);
+        let total = choices.len();
 
-        choice::to_values(chooser(choices))
+        let chosen = chooser(choices);
+        postcondition!(unique_choices(&chosen, total));
+
+        choice::to_values(chosen)
     }
 
-    fn into_choices(self, _guard: &'_ Guard) -> Vec<Choice<'_, T>> {
-        // TODO: check optimization. This is probably optimized well since
-        // choices should have the same size and alignment as T so the collection
-        // may not need to reallocate
-        self.choices
-            .into_iter()
-            .map(|t| Choice::with_guard(t, _guard))
-            .collect()
+    /// Like [with](Selector::with), but the chooser is handed a [ChoiceSet], which knows how
+    /// many choices there are and dereferences to the usual [Vec] of choices.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let [middle] = select_from((1..=9).filter(|n| n % 2 == 1)).with_set(|mut choices| {
+    ///     let half = choices.len() / 2;
+    ///     [choices.remove(half)]
+    /// });
+    ///
+    /// assert_eq!(middle, 5);
+    /// ```
+    pub fn with_set<const K: usize, C>(self, chooser: C) -> [T; K]
+    where
+        C: FnOnce(ChoiceSet<'_, T>) -> [Choice<'_, T>; K],
+    {
+        self.with(|choices| chooser(ChoiceSet::new(choices)))
+    }
+
+    /// Like [any_with](Selector::any_with), but the chooser is handed a [ChoiceSet], see
+    /// [with_set](Selector::with_set).
+    pub fn any_with_set<C>(self, chooser: C) -> Vec<T>
+    where
+        C: FnOnce(ChoiceSet<'_, T>) -> Vec<Choice<'_, T>>,
+    {
+        self.any_with(|choices| chooser(ChoiceSet::new(choices)))
+    }
+
+    /// Like [with](Selector::with), but the chooser is handed the choices as a lazy iterator
+    /// instead of a [Vec]. Values are only pulled from the choices as the chooser advances
+    /// the iterator, so a chooser that stops early never produces the rest, and dropping the
+    /// iterator drops whatever is left unpulled.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// // there are infinitely many choices, but only the first few are ever looked at
+    /// let [first, second] = select_from(1..).with_iter(|mut choices| {
+    ///     let mut squares = choices.by_ref().filter(|c| (1..=**c).any(|r| r * r == **c));
+    ///     [squares.next().unwrap(), squares.next().unwrap()]
+    /// });
+    ///
+    /// assert_eq!([first, second], [1, 4]);
+    /// ```
+    /// The choices can't be kept past the call, same as with a [Vec]:
+    /// ```compile_fail
+    /// use choose_from::select_from;
+    ///
+    /// let mut smuggler = Vec::new();
+    ///
+    /// select_from(1..).with_iter(|mut choices| {
+    ///     smuggler.push(choices.next().unwrap());
+    ///     [choices.next().unwrap()]
+    /// });
+    /// ```
+    pub fn with_iter<const K: usize, C>(self, chooser: C) -> [T; K]
+    where
+        C: FnOnce(LazyChoices<'_, I::IntoIter>) -> [Choice<'_, T>; K],
+    {
+        let _guard = Guard;
+
+        let chosen = chooser(LazyChoices::new(self.choices.into_iter(), &_guard));
+        // how many choices there were isn't known without pulling the rest
+        postcondition!(unique_choices(&chosen, usize::MAX));
+
+        chosen.map(Choice::into_inner)
+    }
+
+    /// Like [any_with](Selector::any_with), but the chooser is handed the choices as a lazy
+    /// iterator, see [with_iter](Selector::with_iter).
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let chosen = select_from(["a", "bb", "ccc", "dddd"]).any_with_iter(|choices| {
+    ///     choices.take_while(|c| c.len() < 3).collect()
+    /// });
+    ///
+    /// assert_eq!(chosen, ["a", "bb"]);
+    /// ```
+    pub fn any_with_iter<C>(self, chooser: C) -> Vec<T>
+    where
+        C: FnOnce(LazyChoices<'_, I::IntoIter>) -> Vec<Choice<'_, T>>,
+    {
+        let _guard = Guard;
+
+        let chosen = chooser(LazyChoices::new(self.choices.into_iter(), &_guard));
+        postcondition!(unique_choices(&chosen, usize::MAX));
+
+        choice::to_values(chosen)
+    }
+
+    /// Presents the choices one at a time, in their original order, and lets `decide` keep or
+    /// discard each one. At most `limit` choices are kept: once the limit is reached any further
+    /// [Swipe::Keep] is refused and counted in [SwipeState::refused]. The kept values are
+    /// returned along with how many keeps were refused in total, including the last choice's.
+    /// ```
+    /// use choose_from::select_from;
+    /// use choose_from::swipe::Swipe;
+    ///
+    /// let (kept, refused) = select_from(1..=10).swipe_with(2, |choice, _| {
+    ///     if *choice % 3 == 0 {
+    ///         Swipe::Keep(choice)
+    ///     } else {
+    ///         Swipe::Discard(choice)
+    ///     }
+    /// });
+    ///
+    /// // 9 was refused since we can only keep two
+    /// assert_eq!(kept, [3, 6]);
+    /// assert_eq!(refused, 1);
+    /// ```
+    pub fn swipe_with<F>(self, limit: usize, mut decide: F) -> (Vec<T>, usize)
+    where
+        F: FnMut(Choice<'_, T>, SwipeState) -> Swipe<'_, T>,
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+        let total = choices.len();
+
+        let mut state = SwipeState {
+            keeps_remaining: limit,
+            options_left: total,
+            refused: 0,
+        };
+        let mut kept = Vec::new();
+
+        for choice in choices {
+            state.options_left -= 1;
+
+            match decide(choice, state) {
+                Swipe::Keep(choice) if state.keeps_remaining > 0 => {
+                    state.keeps_remaining -= 1;
+                    kept.push(choice);
+                }
+                Swipe::Keep(_) => state.refused += 1,
+                Swipe::Discard(_) => {}
+            }
+        }
+        postcondition!(unique_choices(&kept, total));
+
+        (choice::to_values(kept), state.refused)
+    }
+
+    /// Presents the choices in pages of `chunk_size`, in their original order, calling
+    /// `chooser` once per page. The choices returned from every page are collected into the
+    /// selection. Returning [ControlFlow::Break] keeps the choices returned with it and stops
+    /// paging, leaving the rest of the choices unseen. The last page holds whatever is left,
+    /// so it may be shorter, and with no choices at all `chooser` is never called.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    /// ```
+    /// use choose_from::select_from;
+    /// use std::ops::ControlFlow;
+    ///
+    /// let mut pages = 0;
+    ///
+    /// let chosen = select_from(1..=100).chunks_with(20, |page| {
+    ///     pages += 1;
+    ///     let picked: Vec<_> = page.into_iter().filter(|c| **c % 15 == 0).collect();
+    ///
+    ///     // stop as soon as something was found
+    ///     if picked.is_empty() {
+    ///         ControlFlow::Continue(picked)
+    ///     } else {
+    ///         ControlFlow::Break(picked)
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(chosen, [15]);
+    /// assert_eq!(pages, 1);
+    /// ```
+    pub fn chunks_with<C>(self, chunk_size: usize, mut chooser: C) -> Vec<T>
+    where
+        C: FnMut(Vec<Choice<'_, T>>) -> ControlFlow<Vec<Choice<'_, T>>, Vec<Choice<'_, T>>>,
+    {
+        assert!(
+            chunk_size > 0,
+            "chunks_with needs a chunk size of at least 1"
+        );
+
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+        let total = choices.len();
+
+        let mut choices = choices.into_iter();
+        let mut chosen = Vec::new();
+
+        loop {
+            let page: Vec<_> = choices.by_ref().take(chunk_size).collect();
+            if page.is_empty() {
+                break;
+            }
+
+            match chooser(page) {
+                ControlFlow::Continue(picked) => chosen.extend(picked),
+                ControlFlow::Break(picked) => {
+                    chosen.extend(picked);
+                    break;
+                }
+            }
+        }
+        postcondition!(unique_choices(&chosen, total));
+
+        choice::to_values(chosen)
+    }
+
+    /// Like [any_with](Selector::any_with), but the chosen values are collected into any
+    /// [FromIterator] collection instead of a [Vec].
+    /// ```
+    /// use std::collections::HashSet;
+    /// use choose_from::select_from;
+    ///
+    /// let chosen: HashSet<_> = select_from(vec!["Hi", "how", "are ya?"]).collect_with(|choices| {
+    ///     choices.into_iter().filter(|c| c.len() > 2).collect()
+    /// });
+    ///
+    /// assert!(chosen.contains("how"));
+    /// assert_eq!(chosen.len(), 2);
+    /// ```
+    // the chooser still returns a Vec: the returned collection would have to be generic over the
+    // guard lifetime, which a single type parameter can't express for closures
+    pub fn collect_with<B, C>(self, chooser: C) -> B
+    where
+        B: FromIterator<T>,
+        C: FnOnce(Vec<Choice<'_, T>>) -> Vec<Choice<'_, T>>,
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+        let total = choices.len();
+
+        let chosen = chooser(choices);
+        postcondition!(unique_choices(&chosen, total));
+
+        chosen.into_iter().map(Choice::into_inner).collect()
+    }
+
+    /// Like [any_with](Selector::any_with), but the chosen values are returned in the order the
+    /// choices were presented, no matter what order the chooser returned them in.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let chosen = select_from(vec!["ham", "olive", "onion", "pepper", "basil"]).ordered_with(|choices| {
+    ///     // the user ticked the boxes from the bottom up
+    ///     choices.into_iter().rev().step_by(2).collect()
+    /// });
+    ///
+    /// assert_eq!(chosen, ["ham", "onion", "basil"]);
+    /// ```
+    pub fn ordered_with<C>(self, chooser: C) -> Vec<T>
+    where
+        C: FnOnce(Vec<IndexedChoice<'_, T>>) -> Vec<IndexedChoice<'_, T>>,
+    {
+        let mut slots = Slots::new(self.choices);
+        let total = slots.len();
+        let mut chosen: Vec<usize> = chooser(slots.choices())
+            .iter()
+            .map(IndexedChoice::index)
+            .collect();
+        postcondition!(unique_indices(chosen.iter().copied(), total));
+
+        chosen.sort_unstable();
+        slots.take_all(chosen)
+    }
+
+    /// Has `chooser` rank every choice, by returning all of them in order of preference. Since
+    /// a choice can't be returned twice, returning as many choices as there were means none
+    /// were left out. Leaving any out is rejected with a
+    /// [WrongCount](crate::error::ChooseErrorKind::WrongCount) error.
+    /// ```
+    /// use choose_from::select_from;
+    /// use choose_from::error::WrongSelectionCount;
+    ///
+    /// let candidates = vec!["ann", "bob", "cid"];
+    ///
+    /// let ballot = select_from(candidates.clone()).ranked_with(|mut choices| {
+    ///     choices.rotate_left(1);
+    ///     choices
+    /// });
+    /// assert_eq!(ballot, Ok(vec!["bob", "cid", "ann"]));
+    ///
+    /// let spoiled = select_from(candidates).ranked_with(|mut choices| {
+    ///     choices.pop();
+    ///     choices
+    /// });
+    /// assert_eq!(spoiled.unwrap_err().as_wrong_count(), Some(&WrongSelectionCount { expected: 3, actual: 2 }));
+    /// ```
+    pub fn ranked_with<C>(self, chooser: C) -> Result<Vec<T>, ChooseError>
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> Vec<Choice<'_, T>>,
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+        let total = choices.len();
+
+        let ranked = chooser(choices);
+        if ranked.len() != total {
+            return Err(WrongSelectionCount {
+                expected: total,
+                actual: ranked.len(),
+            }
+            .into());
+        }
+        postcondition!(unique_choices(&ranked, total));
+
+        Ok(choice::to_values(ranked))
+    }
+
+    /// Chooses the value with the highest score, returned along with its score, or `None` if
+    /// there are no choices. If several values share the highest score, the first one is
+    /// chosen. Scores that aren't comparable to themselves, like a NaN, are treated as lowest.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let moves = vec!["advance", "retreat", "hold"];
+    ///
+    /// let best = select_from(moves).best_with(|m| match *m {
+    ///     "advance" => 0.4,
+    ///     "retreat" => f64::NAN,
+    ///     _ => 0.7,
+    /// });
+    /// assert_eq!(best, Some(("hold", 0.7)));
+    ///
+    /// assert_eq!(select_from(Vec::<&str>::new()).best_with(|m| m.len()), None);
+    /// ```
+    pub fn best_with<S, F>(self, score: F) -> Option<(T, S)>
+    where
+        S: PartialOrd,
+        F: FnMut(&T) -> S,
+    {
+        self.best_k_with(1, score).pop()
+    }
+
+    /// Chooses the `k` values with the highest scores, from best to worst, each returned with
+    /// its score. Values with equal scores keep their original order, and if there are fewer
+    /// than `k` choices all of them are returned. Scores are compared like in
+    /// [best_with](Selector::best_with).
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let players = vec![("ann", 3), ("bob", 5), ("cid", 3), ("dee", 1)];
+    ///
+    /// let top = select_from(players).best_k_with(3, |p| p.1);
+    ///
+    /// // ann and cid are tied, so they stay in their original order
+    /// assert_eq!(top, [(("bob", 5), 5), (("ann", 3), 3), (("cid", 3), 3)]);
+    /// ```
+    pub fn best_k_with<S, F>(self, k: usize, mut score: F) -> Vec<(T, S)>
+    where
+        S: PartialOrd,
+        F: FnMut(&T) -> S,
+    {
+        let mut scores = Vec::new();
+
+        let chosen = self.any_with(|choices| {
+            let mut scored: Vec<_> = choices
+                .into_iter()
+                .map(|c| {
+                    let s = score(&c);
+                    (c, s)
+                })
+                .collect();
+            // sort_by is stable, so ties keep their original order
+            scored.sort_by(|(_, a), (_, b)| compare_scores(b, a));
+            scored.truncate(k);
+
+            let chosen;
+            (chosen, scores) = scored.into_iter().unzip();
+            chosen
+        });
+
+        chosen.into_iter().zip(scores).collect()
+    }
+
+    /// Like [any_with](Selector::any_with), but the values that weren't chosen are returned too,
+    /// in their original order.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let (hand, deck) = select_from(vec!["ace", "king", "queen", "jack"]).partition_with(|choices| {
+    ///     choices.into_iter().filter(|c| c.starts_with('q') || c.starts_with('k')).collect()
+    /// });
+    ///
+    /// assert_eq!(hand, ["king", "queen"]);
+    /// assert_eq!(deck, ["ace", "jack"]);
+    /// ```
+    pub fn partition_with<C>(self, chooser: C) -> (Vec<T>, Vec<T>)
+    where
+        C: FnOnce(Vec<IndexedChoice<'_, T>>) -> Vec<IndexedChoice<'_, T>>,
+    {
+        let mut slots = Slots::new(self.choices);
+        let total = slots.len();
+        let chosen: Vec<usize> = chooser(slots.choices())
+            .iter()
+            .map(IndexedChoice::index)
+            .collect();
+        postcondition!(unique_indices(chosen.iter().copied(), total));
+
+        let (chosen, rest) = (slots.take_all(chosen), slots.into_rest());
+        postcondition!(partition(chosen.len(), rest.len(), total));
+
+        (chosen, rest)
+    }
+
+    /// Like [with](Selector::with), but the values that weren't chosen are returned too,
+    /// in their original order.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let ([a, b], rest) = select_from(vec![1, 1, 2]).with_rest(|mut choices| {
+    ///     // take the second 1 and the 2
+    ///     [choices.remove(1), choices.remove(1)]
+    /// });
+    ///
+    /// assert_eq!([a, b], [1, 2]);
+    /// assert_eq!(rest, [1]);
+    /// ```
+    pub fn with_rest<const K: usize, C>(self, chooser: C) -> ([T; K], Vec<T>)
+    where
+        C: FnOnce(Vec<IndexedChoice<'_, T>>) -> [IndexedChoice<'_, T>; K],
+    {
+        let mut slots = Slots::new(self.choices);
+        let total = slots.len();
+        let chosen = chooser(slots.choices()).map(|c| c.index());
+        postcondition!(unique_indices(chosen, total));
+
+        let chosen = chosen.map(|i| slots.take(i));
+        let rest = slots.into_rest();
+        postcondition!(partition(K, rest.len(), total));
+
+        (chosen, rest)
+    }
+
+    /// The inverse of [with](Selector::with): `chooser` returns the K choices to remove, and
+    /// every other value is returned, in its original order. The removed values are dropped.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let hand = vec!["two", "king", "two", "ace"];
+    ///
+    /// // discard two cards, the first two and the king
+    /// let kept = select_from(hand).reject_with(|mut choices| {
+    ///     [choices.remove(0), choices.remove(0)]
+    /// });
+    ///
+    /// assert_eq!(kept, ["two", "ace"]);
+    /// ```
+    pub fn reject_with<const K: usize, C>(self, chooser: C) -> Vec<T>
+    where
+        C: FnOnce(Vec<IndexedChoice<'_, T>>) -> [IndexedChoice<'_, T>; K],
+    {
+        self.with_rest(chooser).1
+    }
+
+    /// Like [reject_with](Selector::reject_with), but any number of choices can be removed.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let kept = select_from(1..=6).reject_any_with(|choices| {
+    ///     choices.into_iter().filter(|c| **c % 3 == 0).collect()
+    /// });
+    ///
+    /// assert_eq!(kept, [1, 2, 4, 5]);
+    /// ```
+    pub fn reject_any_with<C>(self, chooser: C) -> Vec<T>
+    where
+        C: FnOnce(Vec<IndexedChoice<'_, T>>) -> Vec<IndexedChoice<'_, T>>,
+    {
+        self.partition_with(chooser).1
+    }
+
+    /// Like [with](Selector::with), but `finalize` is run on each chosen value before it is
+    /// returned, along with the value's position among the choices (see
+    /// [IndexedChoice::index](crate::choice::IndexedChoice::index)). The finalizer runs after
+    /// the chooser returns, so the chooser can't observe or trigger it.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// // (card, picked at position)
+    /// let hand = vec![("ace", None), ("king", None), ("queen", None)];
+    ///
+    /// let picked = select_from(hand).with_finalize(
+    ///     |mut choices| [choices.pop().unwrap(), choices.remove(0)],
+    ///     |card, index| card.1 = Some(index),
+    /// );
+    ///
+    /// assert_eq!(picked, [("queen", Some(2)), ("ace", Some(0))]);
+    /// ```
+    pub fn with_finalize<const K: usize, C, F>(self, chooser: C, mut finalize: F) -> [T; K]
+    where
+        C: FnOnce(Vec<IndexedChoice<'_, T>>) -> [IndexedChoice<'_, T>; K],
+        F: FnMut(&mut T, usize),
+    {
+        let mut slots = Slots::new(self.choices);
+        let total = slots.len();
+        let chosen = chooser(slots.choices()).map(|c| c.index());
+        postcondition!(unique_indices(chosen, total));
+
+        chosen.map(|index| {
+            let mut value = slots.take(index);
+            finalize(&mut value, index);
+            value
+        })
+    }
+
+    /// Like [with_rest](Selector::with_rest), but `finalize` is run on each chosen value and
+    /// `finalize_rest` on each value that wasn't chosen, along with their positions among the
+    /// choices. See [with_finalize](Selector::with_finalize).
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// // (card, face up)
+    /// let board = vec![("ace", false), ("king", false), ("queen", false)];
+    ///
+    /// let ([picked], rest) = select_from(board).with_finalize_rest(
+    ///     |mut choices| [choices.remove(1)],
+    ///     |card, _| card.1 = true,
+    ///     |card, index| assert_ne!((card.0, index), ("king", 1)),
+    /// );
+    ///
+    /// assert_eq!(picked, ("king", true));
+    /// assert_eq!(rest, [("ace", false), ("queen", false)]);
+    /// ```
+    pub fn with_finalize_rest<const K: usize, C, F, R>(
+        self,
+        chooser: C,
+        mut finalize: F,
+        mut finalize_rest: R,
+    ) -> ([T; K], Vec<T>)
+    where
+        C: FnOnce(Vec<IndexedChoice<'_, T>>) -> [IndexedChoice<'_, T>; K],
+        F: FnMut(&mut T, usize),
+        R: FnMut(&mut T, usize),
+    {
+        let mut slots = Slots::new(self.choices);
+        let total = slots.len();
+        let chosen = chooser(slots.choices()).map(|c| c.index());
+        postcondition!(unique_indices(chosen, total));
+
+        let chosen = chosen.map(|index| {
+            let mut value = slots.take(index);
+            finalize(&mut value, index);
+            value
+        });
+        let rest: Vec<T> = slots
+            .into_rest_indexed()
+            .into_iter()
+            .map(|(index, mut value)| {
+                finalize_rest(&mut value, index);
+                value
+            })
+            .collect();
+        postcondition!(partition(K, rest.len(), total));
+
+        (chosen, rest)
+    }
+
+    /// Like [any_with](Selector::any_with), but the number of chosen values must be exactly `k`.
+    /// This is useful when the number of values to choose is only known at runtime.
+    /// ```
+    /// use choose_from::select_from;
+    /// use choose_from::error::WrongSelectionCount;
+    ///
+    /// // imagine this comes from the game state
+    /// let n = 2;
+    ///
+    /// let chosen = select_from(vec![1, 2, 3, 4]).exact_with(n, |choices| {
+    ///     choices.into_iter().take(n).collect()
+    /// });
+    /// assert_eq!(chosen, Ok(vec![1, 2]));
+    ///
+    /// let wrong = select_from(vec![1, 2, 3, 4]).exact_with(n, |choices| {
+    ///     choices.into_iter().take(3).collect()
+    /// });
+    /// assert_eq!(wrong.unwrap_err().as_wrong_count(), Some(&WrongSelectionCount { expected: 2, actual: 3 }));
+    /// ```
+    pub fn exact_with<C>(self, k: usize, chooser: C) -> Result<Vec<T>, ChooseError>
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> Vec<Choice<'_, T>>,
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+        let total = choices.len();
+
+        let chosen = chooser(choices);
+        postcondition!(unique_choices(&chosen, total));
+
+        if chosen.len() != k {
+            return Err(WrongSelectionCount {
+                expected: k,
+                actual: chosen.len(),
+            }
+            .into());
+        }
+
+        Ok(choice::to_values(chosen))
+    }
+
+    /// Lets `chooser` allocate `total` copies across the choices, by returning each choice
+    /// it takes along with how many times it is taken. The counts must add up to exactly
+    /// `total`, otherwise a [WrongCount](crate::error::ChooseErrorKind::WrongCount) error is
+    /// returned with the actual sum. Choices given a count of zero are left out of the result.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// // spend 10 points across the stats
+    /// let stats = vec!["strength", "agility", "wits"];
+    ///
+    /// let spent = select_from(stats).allocate_with(10, |choices| {
+    ///     choices.into_iter().zip([6, 0, 4]).collect()
+    /// });
+    /// assert_eq!(spent, Ok(vec![("strength", 6), ("wits", 4)]));
+    /// ```
+    pub fn allocate_with<C>(self, total: usize, chooser: C) -> Result<Vec<(T, usize)>, ChooseError>
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> Vec<(Choice<'_, T>, usize)>,
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+        let presented = choices.len();
+
+        let allocated = chooser(choices);
+        postcondition!(unique_choices(allocated.iter().map(|(c, _)| c), presented));
+
+        // saturating, so an overflowing allocation is still reported as too large
+        let actual = allocated
+            .iter()
+            .fold(0usize, |sum, (_, count)| sum.saturating_add(*count));
+        if actual != total {
+            return Err(WrongSelectionCount {
+                expected: total,
+                actual,
+            }
+            .into());
+        }
+
+        Ok(allocated
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(c, count)| (c.into_inner(), count))
+            .collect())
+    }
+
+    /// Like [any_with](Selector::any_with), but the number of chosen values must be within
+    /// `bounds`. On failure the error carries the bounds and how many values were chosen,
+    /// so a caller can ask again.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// // pick 2 to 4 toppings
+    /// let chosen = select_from(vec!["ham", "olive", "onion", "pepper", "basil"]).bounded_with(2..=4, |choices| {
+    ///     choices.into_iter().take(3).collect()
+    /// });
+    /// assert_eq!(chosen, Ok(vec!["ham", "olive", "onion"]));
+    ///
+    /// let wrong = select_from(vec!["ham", "olive", "onion", "pepper", "basil"]).bounded_with(2..=4, |choices| {
+    ///     choices.into_iter().take(1).collect()
+    /// });
+    /// assert_eq!(wrong.unwrap_err().as_count_out_of_range().unwrap().actual, 1);
+    /// ```
+    pub fn bounded_with<R, C>(self, bounds: R, chooser: C) -> Result<Vec<T>, ChooseError>
+    where
+        R: RangeBounds<usize>,
+        C: FnOnce(Vec<Choice<'_, T>>) -> Vec<Choice<'_, T>>,
+    {
+        let _guard = Guard;
+        let choices = self.into_choices(&_guard);
+        let total = choices.len();
+
+        let chosen = chooser(choices);
+        postcondition!(unique_choices(&chosen, total));
+
+        SelectionCountError::check(&bounds, chosen.len())?;
+
+        Ok(choice::to_values(chosen))
+    }
+
+    /// Like [any_with](Selector::any_with), but no two chosen values may share a key. The
+    /// key is decided by the caller rather than the chooser, so values that look the same to
+    /// the chooser but are different (or the other way around) can be told apart. An empty
+    /// selection is always distinct. On failure the error names the first key found twice,
+    /// see [DuplicateKey].
+    /// ```
+    /// use choose_from::select_from;
+    /// use choose_from::error::DuplicateKey;
+    ///
+    /// // (id, name), the chooser only cares about the name but the ids must be unique
+    /// let users = vec![(1, "ann"), (2, "bob"), (1, "ann (old account)")];
+    ///
+    /// let chosen = select_from(users.clone()).distinct_by_with(|user| user.0, |choices| {
+    ///     choices.into_iter().take(2).collect()
+    /// });
+    /// assert_eq!(chosen, Ok(vec![(1, "ann"), (2, "bob")]));
+    ///
+    /// let duplicate = select_from(users).distinct_by_with(|user| user.0, |choices| choices);
+    /// assert_eq!(
+    ///     duplicate.unwrap_err().as_duplicate_key(),
+    ///     Some(&DuplicateKey { key: String::from("1") })
+    /// );
+    /// ```
+    pub fn distinct_by_with<K, F, C>(self, key: F, chooser: C) -> Result<Vec<T>, ChooseError>
+    where
+        K: Eq + Hash + Debug,
+        F: Fn(&T) -> K,
+        C: FnOnce(Vec<Choice<'_, T>>) -> Vec<Choice<'_, T>>,
+    {
+        let chosen = self.any_with(chooser);
+
+        match first_duplicate(chosen.iter().map(key)) {
+            Some(key) => Err(DuplicateKey {
+                key: format!("{key:?}"),
+            }
+            .into()),
+            None => Ok(chosen),
+        }
+    }
+
+    /// Like [any_with](Selector::any_with), but the chosen values are collected into a
+    /// [HashSet]. Choices are always distinct, but their values don't have to be: if the
+    /// chooser picks two equal values, the selection fails naming the value, see
+    /// [DuplicateSelection].
+    /// ```
+    /// use choose_from::select_from;
+    /// use choose_from::error::DuplicateSelection;
+    ///
+    /// let tags = vec!["red", "blue", "red"];
+    ///
+    /// let chosen = select_from(tags.clone()).set_with(|choices| choices.into_iter().take(2).collect());
+    /// assert_eq!(chosen.unwrap().len(), 2);
+    ///
+    /// let duplicate = select_from(tags).set_with(|choices| choices);
+    /// assert_eq!(
+    ///     duplicate.unwrap_err().as_duplicate_selection(),
+    ///     Some(&DuplicateSelection { value: String::from("\"red\"") })
+    /// );
+    /// ```
+    pub fn set_with<C>(self, chooser: C) -> Result<HashSet<T>, ChooseError>
+    where
+        T: Eq + Hash + Debug,
+        C: FnOnce(Vec<Choice<'_, T>>) -> Vec<Choice<'_, T>>,
+    {
+        let chosen = self.any_with(chooser);
+
+        let mut set = HashSet::with_capacity(chosen.len());
+        for value in chosen {
+            if set.contains(&value) {
+                return Err(DuplicateSelection {
+                    value: format!("{value:?}"),
+                }
+                .into());
+            }
+            set.insert(value);
+        }
+
+        Ok(set)
+    }
+
+    /// Like [distinct_by_with](Selector::distinct_by_with), but the key is borrowed from the
+    /// value, so it doesn't need to be cloned for every chosen value.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let names = vec![String::from("ann"), String::from("bob"), String::from("ann")];
+    ///
+    /// let duplicate = select_from(names).distinct_by_ref_with(String::as_str, |choices| choices);
+    /// assert_eq!(duplicate.unwrap_err().as_duplicate_key().unwrap().key, "\"ann\"");
+    /// ```
+    pub fn distinct_by_ref_with<K, F, C>(self, key: F, chooser: C) -> Result<Vec<T>, ChooseError>
+    where
+        K: Eq + Hash + Debug + ?Sized,
+        F: Fn(&T) -> &K,
+        C: FnOnce(Vec<Choice<'_, T>>) -> Vec<Choice<'_, T>>,
+    {
+        let chosen = self.any_with(chooser);
+
+        match first_duplicate(chosen.iter().map(key)) {
+            Some(key) => Err(DuplicateKey {
+                key: format!("{key:?}"),
+            }
+            .into()),
+            None => Ok(chosen),
+        }
+    }
+
+    /// Shows the chooser a view of each value made by `f`, instead of the values themselves.
+    /// The chooser picks from the views, and the original values behind the chosen views are
+    /// returned. Useful for hiding parts of a value from the chooser.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// // (internal id, display name)
+    /// let items = vec![(101, "sword"), (102, "shield")];
+    ///
+    /// let [item] = select_from(items)
+    ///     .map_choices(|item| item.1)
+    ///     .with(|mut choices| [choices.remove(0)]);
+    ///
+    /// assert_eq!(item, (101, "sword"));
+    /// ```
+    pub fn map_choices<U, F>(self, f: F) -> MappedSelector<T, U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        let originals: Vec<T> = self.choices.into_iter().collect();
+        let views = originals.iter().map(f).collect();
+
+        MappedSelector::with_views(originals, views)
+    }
+
+    /// Narrows the choices down to the values satisfying `pred` before the chooser sees them.
+    /// Like the other adapters this is lazy, nothing is collected until a selection is made.
+    ///
+    /// The adapters don't keep track of where a value came from, so positions reported by the
+    /// selection (like [with_indices](Selector::with_indices) or
+    /// [IndexedChoice::index](crate::choice::IndexedChoice::index)) count the adapted choices:
+    /// here only the values that satisfy `pred`.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let gold = 5;
+    /// // (card, cost)
+    /// let shop = vec![("knight", 3), ("dragon", 8), ("archer", 2)];
+    ///
+    /// let bought = select_from(shop)
+    ///     .filter_choices(|card| card.1 <= gold)
+    ///     .any_with(|choices| choices);
+    ///
+    /// assert_eq!(bought, [("knight", 3), ("archer", 2)]);
+    /// ```
+    pub fn filter_choices<P>(self, pred: P) -> Selector<Filter<I::IntoIter, P>, T>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        Selector::with_choices(self.choices.into_iter().filter(pred))
+    }
+
+    /// Only offers the first `n` values to the chooser, see [Iterator::take]. Positions
+    /// count the adapted choices, see [filter_choices](Selector::filter_choices).
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let first = select_from(1..).take(3).any_with(|choices| choices);
+    /// assert_eq!(first, [1, 2, 3]);
+    /// ```
+    pub fn take(self, n: usize) -> Selector<Take<I::IntoIter>, T> {
+        Selector::with_choices(self.choices.into_iter().take(n))
+    }
+
+    /// Skips the first `n` values, offering the rest to the chooser, see [Iterator::skip].
+    /// Positions count the adapted choices, so the first value offered is at position 0,
+    /// see [filter_choices](Selector::filter_choices).
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let rest = select_from(vec!["burned", "a", "b"]).skip(1).any_with(|choices| choices);
+    /// assert_eq!(rest, ["a", "b"]);
+    /// ```
+    pub fn skip(self, n: usize) -> Selector<Skip<I::IntoIter>, T> {
+        Selector::with_choices(self.choices.into_iter().skip(n))
+    }
+
+    /// Offers the values to the chooser in reverse order, see [Iterator::rev]. Positions
+    /// count the adapted choices, so the last value is at position 0, see
+    /// [filter_choices](Selector::filter_choices).
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let [top] = select_from(vec!["bottom", "middle", "top"])
+    ///     .rev()
+    ///     .with(|mut choices| [choices.remove(0)]);
+    ///
+    /// assert_eq!(top, "top");
+    /// ```
+    pub fn rev(self) -> Selector<Rev<I::IntoIter>, T>
+    where
+        I::IntoIter: DoubleEndedIterator,
+    {
+        Selector::with_choices(self.choices.into_iter().rev())
+    }
+
+    /// Combines the choices of this selector with the choices of `other` into one pool,
+    /// this selector's values first, see [Iterator::chain]. The selectors can be backed by
+    /// different types. Positions count the combined pool, so the values of `other` come
+    /// after every value of this selector, see [filter_choices](Selector::filter_choices).
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let hand = vec!["ace", "king"];
+    /// let board = ["queen"];
+    ///
+    /// let [a, b] = select_from(hand)
+    ///     .chain(select_from(board))
+    ///     .with(|mut choices| [choices.pop().unwrap(), choices.remove(0)]);
+    ///
+    /// assert_eq!([a, b], ["queen", "ace"]);
+    /// ```
+    pub fn chain<J>(self, other: Selector<J, T>) -> Selector<Chain<I::IntoIter, J::IntoIter>, T>
+    where
+        J: IntoIterator<Item = T>,
+    {
+        Selector::with_choices(self.choices.into_iter().chain(other.choices))
+    }
+
+    /// Pairs this selector with `other`, so one chooser can choose from both pools in a
+    /// single decision, see [ZipSelector]. Unlike [chain](Selector::chain) the pools keep
+    /// their own types.
+    pub fn zip<J, B>(self, other: Selector<J, B>) -> ZipSelector<T, B>
+    where
+        J: IntoIterator<Item = B>,
+    {
+        ZipSelector::with_pools(
+            self.choices.into_iter().collect(),
+            other.choices.into_iter().collect(),
+        )
+    }
+
+    /// Checks that there is at least one choice, so that one of them can be chosen without
+    /// the possibility of panicking, see [NonEmptySelector].
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let targets = select_from(vec!["goblin"]).try_into_nonempty().unwrap();
+    /// assert_eq!(targets.one_with(|mut c| c.remove(0)), "goblin");
+    /// ```
+    pub fn try_into_nonempty(self) -> Result<NonEmptySelector<T>, EmptyChoices> {
+        NonEmptySelector::with_choices(self.choices.into_iter().collect()).ok_or(EmptyChoices)
+    }
+
+    /// Like [with](Selector::with), but borrows the selector and chooses from a clone of the
+    /// choices, so the same selector can be used for many selections. The choices are cloned
+    /// on every call, in the same order each time.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let actions = select_from(vec!["attack", "defend", "flee"]);
+    ///
+    /// let [first_turn] = actions.with_cloned(|mut c| [c.remove(0)]);
+    /// let [second_turn] = actions.with_cloned(|mut c| [c.remove(2)]);
+    ///
+    /// assert_eq!([first_turn, second_turn], ["attack", "flee"]);
+    /// ```
+    pub fn with_cloned<const K: usize, C>(&self, chooser: C) -> [T; K]
+    where
+        I: Clone,
+        C: FnOnce(Vec<Choice<'_, T>>) -> [Choice<'_, T>; K],
+    {
+        Selector::with_choices(self.choices.clone()).with(chooser)
+    }
+
+    /// Like [any_with](Selector::any_with), but borrows the selector and chooses from a clone
+    /// of the choices, see [with_cloned](Selector::with_cloned).
+    pub fn any_with_cloned<C>(&self, chooser: C) -> Vec<T>
+    where
+        I: Clone,
+        C: FnOnce(Vec<Choice<'_, T>>) -> Vec<Choice<'_, T>>,
+    {
+        Selector::with_choices(self.choices.clone()).any_with(chooser)
+    }
+
+    /// Checks at compile time that the choices are [NotShared](crate::owned::NotShared), so
+    /// the chooser can't keep a handle to a chosen value past the selection. Returns the
+    /// selector unchanged. Only available with the `nightly` feature.
+    /// ```
+    /// use std::cell::RefCell;
+    /// use choose_from::select_from;
+    ///
+    /// let units = vec![RefCell::new(10), RefCell::new(20)];
+    ///
+    /// let [chosen] = select_from(units)
+    ///     .assert_no_shared_handles()
+    ///     .with(|mut choices| [choices.remove(1)]);
+    ///
+    /// assert_eq!(chosen.into_inner(), 20);
+    /// ```
+    #[cfg(feature = "nightly")]
+    pub fn assert_no_shared_handles(self) -> Selector<I, T>
+    where
+        T: crate::owned::NotShared,
+    {
+        self
+    }
+
+    /// Gives back the choices without making a selection, so a selector can be built before
+    /// knowing whether it will be needed.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let selector = select_from(vec![1, 2, 3]);
+    /// assert_eq!(selector.into_inner(), [1, 2, 3]);
+    /// ```
+    pub fn into_inner(self) -> I {
+        self.choices
+    }
+
+    /// Like [with](Selector::with), but values may be chosen more than once. The chooser gets
+    /// [LimitedChoice]s without a limit, so it can make as many copies of a choice as it likes
+    /// with [duplicate](LimitedChoice::duplicate). The values are cloned for every returned
+    /// copy.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let upgrades = vec!["armor", "speed", "damage"];
+    ///
+    /// let chosen = select_from(upgrades).with_replacement(|mut choices| {
+    ///     let damage = choices.pop().unwrap();
+    ///     [damage.duplicate().unwrap(), choices.remove(0), damage]
+    /// });
+    ///
+    /// assert_eq!(chosen, ["damage", "armor", "damage"]);
+    /// ```
+    /// Copies are still choices of this selection, so they can't be kept or come from
+    /// anywhere else:
+    /// ```compile_fail
+    /// use choose_from::select_from;
+    ///
+    /// let mut smuggler = Vec::new();
+    ///
+    /// select_from(vec![1, 2]).with_replacement(|choices| {
+    ///     smuggler.push(choices[0].duplicate().unwrap());
+    ///     [choices[0].duplicate().unwrap()]
+    /// });
+    /// ```
+    pub fn with_replacement<const K: usize, C>(self, chooser: C) -> [T; K]
+    where
+        T: Clone,
+        C: for<'guard> FnOnce(Vec<LimitedChoice<'guard, T>>) -> [LimitedChoice<'guard, T>; K],
+    {
+        let unlimited = self.choices.into_iter().map(|t| (usize::MAX, t)).collect();
+        LimitedSelector::with_items(unlimited).with(chooser)
+    }
+
+    /// Turns the choices into a pool that is chosen from over several rounds, like players
+    /// taking turns in a draft. See [Draft].
+    pub fn rounds(self) -> Draft<T> {
+        Draft::with_pool(self.choices.into_iter().collect())
+    }
+
+    /// Presents the choices in a random order, so the chooser can't rely on the order they
+    /// were given in. Only available with the `rand` feature, see [ShuffledSelector].
+    #[cfg(feature = "rand")]
+    pub fn shuffled<R>(self, rng: &mut R) -> ShuffledSelector<T>
+    where
+        R: rand::Rng + ?Sized,
+    {
+        ShuffledSelector::with_choices(self.choices.into_iter().collect(), rng)
+    }
+
+    /// Freezes the presentation, so the chooser is shown the choices in exactly this order
+    /// and can't reorder them. The selection also produces an attestation of what was
+    /// presented and chosen, see [FrozenSelector].
+    pub fn frozen(self) -> FrozenSelector<T> {
+        FrozenSelector::with_choices(self.choices.into_iter().collect())
+    }
+
+    pub(crate) fn into_choices(self, _guard: &'_ Guard) -> Vec<Choice<'_, T>> {
+        self.choices
+            .into_iter()
+            .enumerate()
+            .map(|(i, t)| Choice::with_guard(t, i, _guard))
+            .collect()
+    }
+}
+
+impl<I, T> Selector<I, T>
+where
+    I: IntoIterator<Item = T>,
+    for<'a> &'a I: IntoIterator,
+    for<'a> <&'a I as IntoIterator>::IntoIter: ExactSizeIterator,
+{
+    /// The number of choices the chooser will be given, without running a selection. Only
+    /// available for collections that can be iterated by reference with a known length, like
+    /// [Vec] and arrays.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// assert_eq!(select_from(vec![1, 2, 3]).len(), 3);
+    /// ```
+    pub fn len(&self) -> usize {
+        (&self.choices).into_iter().len()
+    }
+
+    /// Whether the chooser would be given no choices at all.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// assert!(select_from(Vec::<i32>::new()).is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<I, T> Selector<I, T>
+where
+    I: IntoIterator<Item = T>,
+    for<'a> &'a I: IntoIterator<Item = &'a T>,
+{
+    /// Looks at the choices in the order the chooser will be given them, without running a
+    /// selection, so a view of the options can be built before choosing. Only available for
+    /// collections that can be iterated by reference, like [Vec] and arrays. The values are
+    /// only borrowed, so nothing can be moved out of the selector.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let menu = select_from(vec!["soup", "salad", "stew"]);
+    ///
+    /// let preview: Vec<String> = menu.peek().enumerate().map(|(i, dish)| format!("{i}: {dish}")).collect();
+    /// assert_eq!(preview, ["0: soup", "1: salad", "2: stew"]);
+    ///
+    /// let [dish] = menu.with(|mut choices| [choices.remove(2)]);
+    /// assert_eq!(dish, "stew");
+    /// ```
+    pub fn peek(&self) -> impl Iterator<Item = &T> {
+        (&self.choices).into_iter()
+    }
+}
+
+impl<T> Selector<Vec<T>, T> {
+    /// Adds a choice after the existing ones, so a pool can be built up piece by piece.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let mut targets = select_from(vec!["orc"]);
+    /// targets.push("troll");
+    ///
+    /// assert_eq!(targets.any_with(|c| c), ["orc", "troll"]);
+    /// ```
+    pub fn push(&mut self, value: T) {
+        self.choices.push(value);
+    }
+}
+
+// growing the choices only needs the backing collection to grow, choices are presented in
+// the order they were added
+impl<I, T> Extend<T> for Selector<I, T>
+where
+    I: IntoIterator<Item = T> + Extend<T>,
+{
+    fn extend<J: IntoIterator<Item = T>>(&mut self, iter: J) {
+        self.choices.extend(iter);
+    }
+}
+
+impl<T> From<Vec<T>> for Selector<Vec<T>, T> {
+    fn from(choices: Vec<T>) -> Self {
+        Selector::with_choices(choices)
+    }
+}
+
+impl<T> FromIterator<T> for Selector<Vec<T>, T> {
+    fn from_iter<J: IntoIterator<Item = T>>(iter: J) -> Self {
+        Selector::with_choices(iter.into_iter().collect())
+    }
+}
+
+// orders scores for best_k_with, where scores that aren't comparable to themselves (NaN)
+// are lower than everything else, and other incomparable pairs count as equal
+fn compare_scores<S: PartialOrd>(a: &S, b: &S) -> Ordering {
+    let comparable = |s: &S| s.partial_cmp(s).is_some();
+
+    match (comparable(a), comparable(b)) {
+        (true, true) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => Ordering::Equal,
+    }
+}
+
+// the first key seen twice, for the distinct selections
+fn first_duplicate<K: Eq + Hash>(keys: impl Iterator<Item = K>) -> Option<K> {
+    let mut seen = HashSet::new();
+
+    for key in keys {
+        if seen.contains(&key) {
+            return Some(key);
+        }
+        seen.insert(key);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{
+        Declined, DuplicateKey, DuplicateSelection, RetriesExhausted, WrongSelectionCount,
+    };
+    use crate::swipe::{Swipe, SwipeState};
+    use crate::{select_from, Choice, Selector};
+    use std::cell::{Cell, RefCell};
+    use std::collections::HashSet;
+    use std::ops::ControlFlow;
+    use std::rc::Rc;
+
+    #[test]
+    fn swipe_refuses_keeps_past_limit() {
+        let mut states = Vec::new();
+
+        let (kept, refused) =
+            select_from(["a", "b", "c", "d", "e"]).swipe_with(2, |choice, state| {
+                states.push(state);
+                Swipe::Keep(choice)
+            });
+
+        assert_eq!(kept, ["a", "b"]);
+        // the state handed out with the last choice doesn't count its own refusal yet
+        assert_eq!(refused, 3);
+        assert_eq!(
+            states.last(),
+            Some(&SwipeState {
+                keeps_remaining: 0,
+                options_left: 0,
+                refused: 2,
+            })
+        );
+        let remaining: Vec<_> = states.iter().map(|s| s.keeps_remaining).collect();
+        assert_eq!(remaining, [2, 1, 0, 0, 0]);
+        let left: Vec<_> = states.iter().map(|s| s.options_left).collect();
+        assert_eq!(left, [4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn swipe_discarding_everything() {
+        let (kept, refused) = select_from(0..3).swipe_with(3, |choice, _| Swipe::Discard(choice));
+
+        assert!(kept.is_empty());
+        assert_eq!(refused, 0);
+    }
+
+    // the guarantee covers the choices, not clones of shared handles made through them
+    #[test]
+    fn shared_handles_can_outlive_the_selection() {
+        let units: Vec<_> = (0..3).map(|hp| Rc::new(RefCell::new(hp))).collect();
+        let mut stash = Vec::new();
+
+        let chosen = select_from(units).any_with(|choices| {
+            stash.extend(choices.iter().map(|c| c.share()));
+            choices.into_iter().skip(2).collect()
+        });
+
+        *stash[2].borrow_mut() += 10;
+        assert_eq!(*chosen[0].borrow(), 12);
+        // the unchosen values were only kept alive by the stash
+        assert_eq!(Rc::strong_count(&stash[0]), 1);
+    }
+
+    #[test]
+    fn lazy_selection_never_pulls_the_tail() {
+        let pulled = Cell::new(0);
+        let rows = (0..1_000_000).inspect(|_| pulled.set(pulled.get() + 1));
+
+        let chosen = select_from(rows)
+            .any_with_iter(|choices| choices.filter(|c| **c % 3 == 0).take(2).collect());
+
+        assert_eq!(chosen, [0, 3]);
+        assert_eq!(pulled.get(), 4);
+    }
+
+    #[test]
+    fn declined_reason_reaches_the_caller() {
+        let chosen = select_from(vec![1, 2]).with_declinable(|mut choices| Ok([choices.remove(1)]));
+        assert_eq!(chosen.unwrap(), [2]);
+
+        let cancelled =
+            select_from(vec![1, 2]).with_declinable::<1, _>(|_| Err(Declined::UserCancelled));
+        assert!(matches!(cancelled, Err(Declined::UserCancelled)));
+
+        let timed_out =
+            select_from(vec![1, 2]).with_declinable::<1, _>(|_| Err(Declined::TimedOut));
+        assert!(matches!(timed_out, Err(Declined::TimedOut)));
+
+        let other = select_from(vec![1, 2])
+            .with_declinable::<1, _>(|_| Err(Declined::Other("ui went away".into())));
+        match other {
+            Err(Declined::Other(err)) => assert_eq!(err.to_string(), "ui went away"),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn retries_present_the_same_choices_until_accepted() {
+        let mut presented = Vec::new();
+
+        let chosen = select_from(vec!["a", "b", "c"]).with_retries(5, |choices| {
+            presented.push(choices.iter().map(|c| **c).collect::<Vec<_>>());
+            match presented.len() {
+                1 => Err("bad index"),
+                2 => Err("too many picks"),
+                _ => Ok(choices.into_iter().skip(1).collect()),
+            }
+        });
+
+        assert_eq!(chosen, Ok(vec!["b", "c"]));
+        assert_eq!(presented, [["a", "b", "c"]; 3]);
+    }
+
+    #[test]
+    fn retries_exhausted_keeps_last_reason() {
+        let mut attempt = 0;
+
+        let err = select_from(vec![1, 2])
+            .with_retries(2, |_| {
+                attempt += 1;
+                Err::<Vec<_>, _>(attempt)
+            })
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            RetriesExhausted {
+                attempts: 2,
+                last_reason: 2
+            }
+        );
+        assert_eq!(err.to_string(), "chooser rejected all 2 attempts");
+    }
+
+    #[test]
+    fn one_with_returns_value() {
+        let chosen = select_from(["a", "b", "c"]).one_with(|mut choices| choices.remove(1));
+
+        assert_eq!(chosen, "b");
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot choose one value from zero choices")]
+    fn one_with_panics_on_empty() {
+        select_from(Vec::<i32>::new()).one_with(|mut choices| choices.remove(0));
+    }
+
+    #[test]
+    fn try_with_ok_and_err() {
+        let ok: Result<_, ()> = select_from(0..4).try_with(|mut choices| Ok([choices.remove(3)]));
+        assert_eq!(ok, Ok([3]));
+
+        let err = select_from(0..4).try_with::<2, _, _>(|_| Err("cancelled"));
+        assert_eq!(err, Err("cancelled"));
+    }
+
+    #[test]
+    fn try_any_with_ok_and_err() {
+        let ok: Result<_, ()> = select_from(0..4).try_any_with(|choices| Ok(choices));
+        assert_eq!(ok, Ok(vec![0, 1, 2, 3]));
+
+        let err = select_from(0..4).try_any_with(|_| Err(42));
+        assert_eq!(err, Err(42));
+    }
+
+    #[test]
+    fn opt_with_picks_and_cancels() {
+        let picked = select_from(vec!["a", "b"]).opt_with(|mut choices| choices.pop().map(|c| [c]));
+        assert_eq!(picked, Some(["b"]));
+
+        let cancelled = select_from(vec!["a", "b"]).opt_with::<2, _>(|_| None);
+        assert_eq!(cancelled, None);
+    }
+
+    #[test]
+    fn exact_with_zero_all_and_mismatch() {
+        assert_eq!(select_from(0..3).exact_with(0, |_| Vec::new()), Ok(vec![]));
+        assert_eq!(select_from(0..3).exact_with(3, |c| c), Ok(vec![0, 1, 2]));
+        assert_eq!(
+            select_from(0..3).exact_with(1, |c| c),
+            Err(WrongSelectionCount {
+                expected: 1,
+                actual: 3
+            }
+            .into())
+        );
+    }
+
+    #[test]
+    fn bounded_with_inclusive_exclusive_unbounded() {
+        use std::ops::Bound;
+
+        fn pick(choices: Vec<Choice<'_, i32>>, n: usize) -> Vec<Choice<'_, i32>> {
+            choices.into_iter().take(n).collect()
+        }
+
+        assert!(select_from(0..5)
+            .bounded_with(2..=4, |c| pick(c, 4))
+            .is_ok());
+        assert!(select_from(0..5)
+            .bounded_with(2..4, |c| pick(c, 4))
+            .is_err());
+        assert!(select_from(0..5).bounded_with(.., |c| pick(c, 0)).is_ok());
+        assert!(select_from(0..5).bounded_with(3.., |c| pick(c, 5)).is_ok());
+
+        let err = select_from(0..5)
+            .bounded_with(..2, |c| pick(c, 2))
+            .unwrap_err();
+        let err = err.as_count_out_of_range().unwrap();
+        assert_eq!(err.start, Bound::Unbounded);
+        assert_eq!(err.end, Bound::Excluded(2));
+        assert_eq!(err.actual, 2);
+    }
+
+    #[test]
+    fn collect_with_sets_maps_and_strings() {
+        use std::collections::{BTreeMap, HashSet};
+
+        let set: HashSet<i32> = select_from(vec![1, 2, 2, 3]).collect_with(|c| c);
+        assert_eq!(set, HashSet::from([1, 2, 3]));
+
+        let map: BTreeMap<&str, u32> = select_from(vec![("b", 2), ("a", 1), ("c", 3)])
+            .collect_with(|c| c.into_iter().filter(|c| c.1 < 3).collect());
+        assert_eq!(map, BTreeMap::from([("a", 1), ("b", 2)]));
+
+        let word: String = select_from("choose".chars())
+            .collect_with(|c| c.into_iter().filter(|c| **c != 'o').collect());
+        assert_eq!(word, "chse");
+    }
+
+    #[test]
+    fn with_indices_survive_reordering() {
+        let chosen = select_from('a'..='f').with_indices(|mut choices| {
+            choices.reverse();
+            let first = choices.remove(0);
+            choices.retain(|c| **c != 'b');
+            [choices.remove(3), first]
+        });
+
+        assert_eq!(chosen, [(0, 'a'), (5, 'f')]);
+    }
+
+    #[test]
+    fn pairs_out_of_order() {
+        let pairs = select_from(0..6).with_pairs(|mut choices| {
+            let five = choices.pop().unwrap();
+            let zero = choices.remove(0);
+            let [one, two, three, four]: [_; 4] = choices.try_into().unwrap();
+            [(five, zero), (three, one), (four, two)]
+        });
+
+        assert_eq!(pairs, [(5, 0), (3, 1), (4, 2)]);
+    }
+
+    #[test]
+    fn related_pairs_accepted() {
+        let pairs = select_from(vec![1, 11, 2, 12]).any_with_pairs_related_by(
+            |a, b| a % 10 == b % 10,
+            |choices| {
+                let [a, b, c, d]: [_; 4] = choices.try_into().unwrap();
+                vec![(a, b), (c, d)]
+            },
+        );
+
+        assert_eq!(pairs, Ok(vec![(1, 11), (2, 12)]));
+    }
+
+    #[test]
+    fn partition_attributes_equal_values_by_instance() {
+        let (chosen, rest) = select_from(vec![(1, 'a'), (2, 'b'), (1, 'c'), (3, 'd')])
+            .partition_with(|mut choices| {
+                // pick the second `1`, then the `3`
+                let three = choices.pop().unwrap();
+                vec![choices.remove(2), three]
+            });
+
+        assert_eq!(chosen, [(1, 'c'), (3, 'd')]);
+        assert_eq!(rest, [(1, 'a'), (2, 'b')]);
+    }
+
+    #[test]
+    fn partition_nothing_and_everything() {
+        let (chosen, rest) = select_from(0..4).partition_with(|_| Vec::new());
+        assert!(chosen.is_empty());
+        assert_eq!(rest, [0, 1, 2, 3]);
+
+        let (chosen, rest) = select_from(0..4).partition_with(|mut c| {
+            c.reverse();
+            c
+        });
+        assert_eq!(chosen, [3, 2, 1, 0]);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn with_rest_removes_the_chosen_instance() {
+        // the values carry an id so we can tell which 1 is which
+        let ([chosen], rest) = select_from(vec![(1, 'a'), (1, 'b'), (2, 'c')])
+            .with_rest(|mut choices| [choices.remove(1)]);
+
+        assert_eq!(chosen, (1, 'b'));
+        assert_eq!(rest, [(1, 'a'), (2, 'c')]);
+    }
+
+    #[test]
+    fn with_rest_keeps_order() {
+        let ([a, b], rest) = select_from(0..6).with_rest(|mut choices| {
+            let last = choices.pop().unwrap();
+            [last, choices.remove(2)]
+        });
+
+        assert_eq!([a, b], [5, 2]);
+        assert_eq!(rest, [0, 1, 3, 4]);
+    }
+
+    #[test]
+    fn ordered_with_undoes_reversal() {
+        let chosen = select_from(0..5).ordered_with(|mut choices| {
+            choices.reverse();
+            choices.remove(1);
+            choices
+        });
+
+        assert_eq!(chosen, [0, 1, 2, 4]);
+    }
+
+    #[test]
+    fn rankings_must_include_every_choice() {
+        let ranked = select_from(vec![3, 1, 2]).ranked_with(|mut choices| {
+            choices.sort_by_key(|c| **c);
+            choices
+        });
+        assert_eq!(ranked, Ok(vec![1, 2, 3]));
+
+        let dropped = select_from(vec![3, 1, 2])
+            .ranked_with(|choices| choices.into_iter().skip(1).collect())
+            .unwrap_err();
+        assert_eq!(
+            dropped.as_wrong_count(),
+            Some(&WrongSelectionCount {
+                expected: 3,
+                actual: 2
+            })
+        );
+
+        assert_eq!(select_from(Vec::<u8>::new()).ranked_with(|c| c), Ok(vec![]));
+    }
+
+    #[test]
+    fn best_choices_break_ties_by_original_order() {
+        let best = select_from(vec!["b", "a", "c", "a2"]).best_with(|s| s.len());
+        assert_eq!(best, Some(("a2", 2)));
+
+        let tied =
+            select_from(vec![('x', 1), ('y', 2), ('z', 2), ('w', 2)]).best_k_with(2, |p| p.1);
+        assert_eq!(tied, [(('y', 2), 2), (('z', 2), 2)]);
+
+        assert_eq!(select_from(Vec::<u8>::new()).best_with(|n| *n), None);
+        assert!(select_from(vec![1, 2]).best_k_with(0, |n| *n).is_empty());
+        assert_eq!(select_from(vec![1, 2]).best_k_with(5, |n| *n).len(), 2);
+    }
+
+    #[test]
+    fn nan_scores_are_lowest() {
+        let scores = [f64::NAN, -1.0, f64::NAN, 0.5];
+
+        let ranked = select_from(0..4).best_k_with(4, |i| scores[*i]);
+        let order: Vec<_> = ranked.iter().map(|(i, _)| *i).collect();
+        assert_eq!(order, [3, 1, 0, 2]);
+
+        let all_nan = select_from(0..2).best_with(|_| f32::NAN);
+        assert_eq!(all_nan.map(|(i, _)| i), Some(0));
+    }
+
+    #[test]
+    fn rejects_remove_only_the_returned_instances() {
+        // (value, tag) pairs let us tell the equal values apart afterwards
+        let kept =
+            select_from(vec![(7, 'a'), (7, 'b'), (3, 'c'), (7, 'd')]).reject_with(|choices| {
+                let mut sevens = choices.into_iter().filter(|c| c.0 == 7);
+                [sevens.nth(1).unwrap()]
+            });
+        assert_eq!(kept, [(7, 'a'), (3, 'c'), (7, 'd')]);
+
+        let kept = select_from(vec![1, 1, 2, 1])
+            .reject_any_with(|mut choices| vec![choices.remove(3), choices.remove(0)]);
+        assert_eq!(kept, [1, 2]);
+
+        let untouched = select_from(vec![1, 1]).reject_any_with(|_| Vec::new());
+        assert_eq!(untouched, [1, 1]);
+    }
+
+    #[test]
+    fn peeking_leaves_the_selector_untouched() {
+        let selector = select_from(vec![String::from("fire"), String::from("ice")]);
+
+        let preview = selector
+            .peek()
+            .map(|s| format!("[{s}]"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert_eq!(preview, "[fire] [ice]");
+        assert_eq!(selector.peek().count(), selector.len());
+
+        let [chosen] = selector.with(|mut choices| [choices.remove(1)]);
+        assert_eq!(chosen, "ice");
+    }
+
+    #[test]
+    fn pages_cover_every_choice_once() {
+        let mut pages = Vec::new();
+
+        let chosen = select_from(0..7).chunks_with(3, |page| {
+            pages.push(page.iter().map(|c| **c).collect::<Vec<_>>());
+            ControlFlow::Continue(page.into_iter().take(1).collect())
+        });
+
+        assert_eq!(chosen, [0, 3, 6]);
+        // the last page only holds what was left
+        assert_eq!(pages, [vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+    }
+
+    #[test]
+    fn paging_can_stop_early() {
+        let mut seen = 0;
+
+        let chosen = select_from(0..10).chunks_with(4, |page| {
+            seen += page.len();
+            if seen < 8 {
+                ControlFlow::Continue(Vec::new())
+            } else {
+                ControlFlow::Break(page.into_iter().rev().collect())
+            }
+        });
+        assert_eq!(chosen, [7, 6, 5, 4]);
+        assert_eq!(seen, 8);
+
+        let mut calls = 0;
+        let all = select_from(vec!['a', 'b']).chunks_with(100, |page| {
+            calls += 1;
+            ControlFlow::Continue(page)
+        });
+        assert_eq!(all, ['a', 'b']);
+        assert_eq!(calls, 1);
+
+        let none = select_from(Vec::<u8>::new()).chunks_with(1, |_| unreachable!());
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "chunks_with needs a chunk size of at least 1")]
+    fn zero_sized_pages() {
+        select_from(vec![1]).chunks_with(0, |page| ControlFlow::Continue(page));
+    }
+
+    #[test]
+    fn too_few_choices_are_caught_before_choosing() {
+        let err = select_from(0..2)
+            .checked_with::<3, _>(|_| unreachable!())
+            .unwrap_err();
+        assert_eq!(
+            err.as_wrong_count(),
+            Some(&WrongSelectionCount {
+                expected: 3,
+                actual: 2
+            })
+        );
+
+        let exact = select_from(0..2).checked_with(|mut c| [c.pop().unwrap(), c.pop().unwrap()]);
+        assert_eq!(exact.unwrap(), [1, 0]);
+        assert_eq!(select_from(0..0).checked_with::<0, _>(|_| []).unwrap(), []);
+    }
+
+    #[test]
+    fn distinct_by_allows_empty_and_finds_later_duplicates() {
+        let empty = select_from(vec![1, 1]).distinct_by_with(|n| *n, |_| Vec::new());
+        assert_eq!(empty, Ok(vec![]));
+
+        // values only collide under the key, and not on the first pair
+        let words = vec!["apple", "banana", "cherry", "avocado"];
+        let duplicate = select_from(words).distinct_by_ref_with(|w| &w[..1], |c| c);
+        assert_eq!(
+            duplicate.unwrap_err().as_duplicate_key(),
+            Some(&DuplicateKey {
+                key: String::from("\"a\"")
+            })
+        );
+    }
+
+    #[test]
+    fn set_rejects_equal_values_from_different_choices() {
+        // the two 2s are different choices, so choosing both is a legitimate selection that
+        // only collides once it becomes a set
+        let duplicate = select_from(vec![2, 7, 2]).set_with(|mut choices| {
+            choices.remove(1);
+            choices
+        });
+        let duplicate = duplicate.unwrap_err();
+        assert_eq!(
+            duplicate.as_duplicate_selection(),
+            Some(&DuplicateSelection {
+                value: String::from("2")
+            })
+        );
+        assert_eq!(
+            duplicate.to_string(),
+            "the value 2 was chosen more than once"
+        );
+
+        let chosen = select_from(vec![2, 7, 2]).set_with(|mut choices| {
+            choices.pop();
+            choices
+        });
+        assert_eq!(chosen, Ok(HashSet::from([2, 7])));
+    }
+
+    #[test]
+    fn replacement_picks_the_same_value_three_times() {
+        let chosen = select_from(vec![String::from("crit"), String::from("heal")])
+            .with_replacement(|mut choices| {
+                let crit = choices.remove(0);
+                assert_eq!(crit.index(), 0);
+                [crit.duplicate().unwrap(), crit.duplicate().unwrap(), crit]
+            });
+
+        assert_eq!(chosen, ["crit", "crit", "crit"]);
+    }
+
+    #[test]
+    fn allocation_must_add_up_to_total() {
+        let exact = select_from(vec!['a', 'b', 'c'])
+            .allocate_with(5, |choices| choices.into_iter().zip([2, 0, 3]).collect());
+        assert_eq!(exact, Ok(vec![('a', 2), ('c', 3)]));
+
+        let over = select_from(vec!['a', 'b'])
+            .allocate_with(5, |choices| choices.into_iter().zip([4, 2]).collect());
+        assert_eq!(
+            over.unwrap_err().as_wrong_count(),
+            Some(&WrongSelectionCount {
+                expected: 5,
+                actual: 6
+            })
+        );
+
+        let under = select_from(vec!['a', 'b'])
+            .allocate_with(5, |mut choices| vec![(choices.remove(1), 1)]);
+        assert_eq!(
+            under.unwrap_err().as_wrong_count(),
+            Some(&WrongSelectionCount {
+                expected: 5,
+                actual: 1
+            })
+        );
+
+        let overflow = select_from(vec!['a', 'b']).allocate_with(5, |choices| {
+            choices.into_iter().zip([usize::MAX, 1]).collect()
+        });
+        assert_eq!(
+            overflow.unwrap_err().as_wrong_count().unwrap().actual,
+            usize::MAX
+        );
+    }
+
+    #[test]
+    fn grown_selector_presents_in_insertion_order() {
+        let mut pool: Selector<Vec<_>, _> = ["a", "b"].into_iter().collect();
+        pool.push("c");
+        pool.extend(["d", "e"]);
+        pool.push("f");
+        pool.extend(std::iter::empty());
+
+        let mut presented = Vec::new();
+        let chosen = pool.any_with(|choices| {
+            presented.extend(choices.iter().map(|c| **c));
+            choices
+        });
+
+        assert_eq!(presented, ["a", "b", "c", "d", "e", "f"]);
+        assert_eq!(chosen, ["a", "b", "c", "d", "e", "f"]);
+    }
+
+    #[test]
+    fn chained_filters_only_show_eligible_values() {
+        let mut seen = Vec::new();
+
+        let chosen = select_from(1..=20)
+            .filter_choices(|n| n % 2 == 0)
+            .filter_choices(|n| n % 3 == 0)
+            .skip(1)
+            .any_with(|choices| {
+                seen.extend(choices.iter().map(|c| **c));
+                choices
+            });
+
+        assert_eq!(seen, [12, 18]);
+        assert_eq!(chosen, [12, 18]);
+    }
+
+    #[test]
+    fn adapted_positions_count_the_adapted_choices() {
+        let chosen = select_from(vec![10, 11, 12, 13, 14])
+            .filter_choices(|n| n % 2 == 1)
+            .with_indices(|mut choices| [choices.pop().unwrap()]);
+        assert_eq!(chosen, [(1, 13)]);
+
+        let chosen = select_from(vec![10, 11, 12])
+            .rev()
+            .skip(1)
+            .with_indices(|mut choices| [choices.remove(1), choices.remove(0)]);
+        assert_eq!(chosen, [(1, 10), (0, 11)]);
+    }
+
+    #[test]
+    fn chain_keeps_order_across_backing_types() {
+        let chosen = select_from(vec![1, 2])
+            .chain(select_from([3, 4]))
+            .chain(select_from(5..=6))
+            .ordered_with(|mut choices| {
+                choices.reverse();
+                choices.into_iter().step_by(2).collect()
+            });
+
+        assert_eq!(chosen, [2, 4, 6]);
+    }
+
+    #[test]
+    fn auto_or_with_zero_one_many() {
+        let mut calls = 0;
+
+        let none = select_from(Vec::<i32>::new()).auto_or_with(|mut c| {
+            calls += 1;
+            c.remove(0)
+        });
+        assert_eq!(none, None);
+
+        let one = select_from(vec![7]).auto_or_with(|mut c| {
+            calls += 1;
+            c.remove(0)
+        });
+        assert_eq!(one, Some(7));
+        assert_eq!(calls, 0);
+
+        let many = select_from(vec![7, 8]).auto_or_with(|mut c| {
+            calls += 1;
+            c.remove(1)
+        });
+        assert_eq!(many, Some(8));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn len_of_backing_collections() {
+        assert_eq!(select_from([1, 2]).len(), 2);
+        assert!(select_from(Vec::<u8>::new()).is_empty());
+        assert!(!select_from(vec![()]).is_empty());
+    }
+
+    #[test]
+    fn cloned_selections_are_independent() {
+        let selector = select_from(vec![String::from("a"), String::from("b")]);
+
+        let first = selector.any_with_cloned(|mut c| {
+            c.reverse();
+            c
+        });
+        let second = selector.any_with_cloned(|c| c);
+
+        assert_eq!(first, ["b", "a"]);
+        assert_eq!(second, ["a", "b"]);
+        // the selector itself is untouched
+        assert_eq!(selector.into_inner(), ["a", "b"]);
+    }
+
+    #[test]
+    fn finalizers_see_original_indices_after_reordering() {
+        let mut seen = Vec::new();
+        let mut seen_rest = Vec::new();
+
+        let (chosen, rest) = select_from(vec!['a', 'b', 'c', 'd']).with_finalize_rest(
+            |mut choices| {
+                choices.reverse();
+                [choices.remove(0), choices.remove(1)]
+            },
+            |c, i| seen.push((*c, i)),
+            |c, i| seen_rest.push((*c, i)),
+        );
+
+        assert_eq!(chosen, ['d', 'b']);
+        assert_eq!(seen, [('d', 3), ('b', 1)]);
+        assert_eq!(rest, ['a', 'c']);
+        assert_eq!(seen_rest, [('a', 0), ('c', 2)]);
+
+        let stamped = select_from(vec![0; 3]).with_finalize(
+            |mut choices| [choices.pop().unwrap(), choices.remove(0)],
+            |n, i| *n = i * 10,
+        );
+        assert_eq!(stamped, [20, 0]);
+    }
+
+    #[test]
+    fn cloned_selectors_choose_independently() {
+        let pool: Vec<String> = ["ann", "bob", "cid", "dee"].map(String::from).into();
+        let selector = select_from(pool.clone());
+
+        let greedy = selector
+            .clone()
+            .any_with(|choices| choices.into_iter().take(3).collect());
+        let picky =
+            selector.any_with(|choices| choices.into_iter().filter(|c| c.contains('d')).collect());
+
+        assert_eq!(greedy, ["ann", "bob", "cid"]);
+        assert_eq!(picky, ["cid", "dee"]);
+        // each selection holds distinct values from the pool on its own
+        for chosen in [greedy, picky] {
+            assert!(chosen.iter().all(|c| pool.contains(c)));
+            assert_eq!(chosen.iter().collect::<HashSet<_>>().len(), chosen.len());
+        }
+
+        let fixed = crate::select_from_fixed([1, 2, 3]);
+        let copied = fixed;
+        assert_eq!(fixed.with(|[a, _, _]| [a]), [1]);
+        assert_eq!(copied.with(|[_, _, c]| [c]), [3]);
     }
 }
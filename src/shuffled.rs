@@ -0,0 +1,154 @@
+//! Selections presented in a random order, see [shuffled](crate::selector::Selector::shuffled).
+//! Only available with the `rand` feature.
+
+use crate::choice::IndexedChoice;
+use crate::{Choice, Selector, SelectorFixed};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Wraps choices that are presented to the chooser in a random order. The chooser only
+/// learns presented positions (through [IndexedChoice::index] as well), while the original
+/// positions stay with the selector.
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct ShuffledSelector<T> {
+    choices: Vec<T>,
+    // the original position of each presented choice
+    original: Vec<usize>,
+}
+
+impl<T> ShuffledSelector<T> {
+    pub(crate) fn with_choices<R>(choices: Vec<T>, rng: &mut R) -> ShuffledSelector<T>
+    where
+        R: Rng + ?Sized,
+    {
+        let mut indexed: Vec<_> = choices.into_iter().enumerate().collect();
+        indexed.shuffle(rng);
+
+        let (original, choices) = indexed.into_iter().unzip();
+        ShuffledSelector { choices, original }
+    }
+
+    /// Like [Selector::with], but the choices are presented in the shuffled order.
+    /// ```
+    /// use choose_from::select_from;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(3);
+    /// let answers = vec!["right", "wrong", "also wrong"];
+    ///
+    /// // the chooser has no way of knowing the right answer came first
+    /// let [picked] = select_from(answers).shuffled(&mut rng).with(|mut choices| {
+    ///     [choices.remove(0)]
+    /// });
+    /// ```
+    pub fn with<const K: usize, C>(self, chooser: C) -> [T; K]
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> [Choice<'_, T>; K],
+    {
+        Selector::with_choices(self.choices).with(chooser)
+    }
+
+    /// Like [Selector::any_with], but the choices are presented in the shuffled order.
+    pub fn any_with<C>(self, chooser: C) -> Vec<T>
+    where
+        C: FnOnce(Vec<Choice<'_, T>>) -> Vec<Choice<'_, T>>,
+    {
+        Selector::with_choices(self.choices).any_with(chooser)
+    }
+
+    /// Like [Selector::with_indices], but the returned indices are the positions the
+    /// chosen values had before shuffling, not the positions they were presented at.
+    /// ```
+    /// use choose_from::select_from;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(3);
+    /// let answers = vec!["right", "wrong", "also wrong"];
+    ///
+    /// let [(index, picked)] = select_from(answers.clone())
+    ///     .shuffled(&mut rng)
+    ///     .with_indices(|mut choices| [choices.remove(0)]);
+    ///
+    /// assert_eq!(answers[index], picked);
+    /// ```
+    pub fn with_indices<const K: usize, C>(self, chooser: C) -> [(usize, T); K]
+    where
+        C: FnOnce(Vec<IndexedChoice<'_, T>>) -> [IndexedChoice<'_, T>; K],
+    {
+        let original = self.original;
+
+        Selector::with_choices(self.choices)
+            .with_indices(chooser)
+            .map(|(i, t)| (original[i], t))
+    }
+}
+
+impl<const N: usize, T> SelectorFixed<N, T> {
+    /// Shuffles the choices, keeping the array form. Unlike [Selector::shuffled] the
+    /// original positions aren't kept, the shuffled array simply becomes the choices. Only
+    /// available with the `rand` feature.
+    /// ```
+    /// use choose_from::select_from_fixed;
+    /// use rand::rngs::StdRng;
+    /// use rand::SeedableRng;
+    ///
+    /// let mut rng = StdRng::seed_from_u64(3);
+    ///
+    /// let [a, _, _] = select_from_fixed([1, 2, 3]).shuffled(&mut rng).with(|[a, b, c]| [a, b, c]);
+    /// assert!((1..=3).contains(&a));
+    /// ```
+    pub fn shuffled<R>(self, rng: &mut R) -> SelectorFixed<N, T>
+    where
+        R: Rng + ?Sized,
+    {
+        let mut choices = self.into_inner();
+        choices.shuffle(rng);
+
+        SelectorFixed::with_choices(choices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{select_from, select_from_fixed};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn seeded_presentation_order() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let mut presented = Vec::new();
+
+        select_from(0..6).shuffled(&mut rng).any_with(|choices| {
+            presented.extend(choices.iter().map(|c| **c));
+            choices
+        });
+
+        assert_eq!(presented, [2, 1, 0, 4, 5, 3]);
+    }
+
+    #[test]
+    fn indices_are_from_before_the_shuffle() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let letters = vec!['a', 'b', 'c', 'd', 'e', 'f'];
+
+        let chosen = select_from(letters.clone())
+            .shuffled(&mut rng)
+            .with_indices(|mut choices| [choices.remove(0), choices.remove(2)]);
+
+        assert_eq!(chosen, [(2, 'c'), (4, 'e')]);
+        for (index, letter) in chosen {
+            assert_eq!(letters[index], letter);
+        }
+    }
+
+    #[test]
+    fn seeded_fixed_shuffle() {
+        let mut rng = StdRng::seed_from_u64(9);
+
+        let shuffled = select_from_fixed([1, 2, 3, 4]).shuffled(&mut rng);
+        assert_eq!(shuffled.into_inner(), [2, 1, 4, 3]);
+    }
+}
@@ -0,0 +1,24 @@
+//! Types for presenting choices one at a time, see [`Selector::swipe_with`](crate::Selector::swipe_with).
+
+use crate::Choice;
+
+/// The decision made about a single choice, handing the choice back to the selector.
+#[derive(Debug)]
+pub enum Swipe<'guard, T> {
+    /// Keep this choice as part of the selection
+    Keep(Choice<'guard, T>),
+    /// Leave this choice out of the selection
+    Discard(Choice<'guard, T>),
+}
+
+/// Progress of a swipe selection, passed along with each choice.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct SwipeState {
+    /// How many more choices may be kept. Once this reaches zero any [Swipe::Keep] is
+    /// refused and treated as a [Swipe::Discard].
+    pub keeps_remaining: usize,
+    /// How many choices are left to present after the current one.
+    pub options_left: usize,
+    /// How many keeps have been refused so far because the limit was reached.
+    pub refused: usize,
+}
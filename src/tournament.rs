@@ -0,0 +1,181 @@
+// A top-K selection driven only by pairwise comparisons. It runs through the regular
+// any_with, so the values it returns are guaranteed to be from the choices like any other.
+
+use crate::{Choice, Selector};
+use std::cmp::Ordering;
+
+impl<I, T> Selector<I, T>
+where
+    I: IntoIterator<Item = T>,
+{
+    /// Chooses the `k` greatest values according to `compare`, from greatest to least,
+    /// asking nothing but pairwise comparisons. Values that compare equal are ranked by
+    /// their original order, and if there are fewer than `k` choices all of them are
+    /// returned, sorted.
+    ///
+    /// The best `k` seen so far are kept in a heap, so each later choice is compared with
+    /// the worst of them first, and only climbs the heap if it beats it. That makes at most
+    /// about `n * (1 + 2 * log2(k))` comparisons for `n` choices, plus about `2 * k * log2(k)`
+    /// to rank the `k` results, which matters when each comparison is a question for a person.
+    /// An inconsistent `compare` gives an arbitrary ranking, but never panics.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let mut questions = 0;
+    ///
+    /// // "which of these two dishes do you like better?"
+    /// let favourites = select_from(vec!["soup", "stew", "pie", "tart", "salad"])
+    ///     .tournament_with(2, |a, b| {
+    ///         questions += 1;
+    ///         a.len().cmp(&b.len()).then(b.cmp(a))
+    ///     });
+    ///
+    /// assert_eq!(favourites, ["salad", "soup"]);
+    /// assert!(questions <= 5 * 3 + 2 * 2);
+    /// ```
+    pub fn tournament_with<F>(self, k: usize, mut compare: F) -> Vec<T>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.any_with(|choices| {
+            // whether `a` ranks above `b`, ties going to the earlier choice
+            let mut beats =
+                |(i, a): &(usize, Choice<'_, T>), (j, b): &(usize, Choice<'_, T>)| match compare(
+                    a, b,
+                ) {
+                    Ordering::Equal => i < j,
+                    ordering => ordering == Ordering::Greater,
+                };
+
+            // the worst of the best k so far is at the root
+            let mut heap = Vec::with_capacity(k.min(choices.len()));
+            for choice in choices.into_iter().enumerate() {
+                if heap.len() < k {
+                    heap.push(choice);
+                    sift_up(&mut heap, &mut beats);
+                } else if k > 0 && beats(&choice, &heap[0]) {
+                    heap[0] = choice;
+                    sift_down(&mut heap, &mut beats);
+                }
+            }
+
+            // popping the root repeatedly gives the worst first, and only relies on `beats`,
+            // which a comparison handed in by the caller may not keep a total order for
+            let mut ranked = Vec::with_capacity(heap.len());
+            while !heap.is_empty() {
+                let last = heap.len() - 1;
+                heap.swap(0, last);
+                ranked.extend(heap.pop().map(|(_, choice)| choice));
+                sift_down(&mut heap, &mut beats);
+            }
+
+            ranked.reverse();
+            ranked
+        })
+    }
+}
+
+// moves the last element up until its parent doesn't rank below it
+fn sift_up<C, F>(heap: &mut [C], beats: &mut F)
+where
+    F: FnMut(&C, &C) -> bool,
+{
+    let mut i = heap.len() - 1;
+
+    while i > 0 {
+        let parent = (i - 1) / 2;
+        if !beats(&heap[parent], &heap[i]) {
+            break;
+        }
+        heap.swap(parent, i);
+        i = parent;
+    }
+}
+
+// moves the root down until none of its children rank below it
+fn sift_down<C, F>(heap: &mut [C], beats: &mut F)
+where
+    F: FnMut(&C, &C) -> bool,
+{
+    let mut i = 0;
+
+    loop {
+        let mut worst = i;
+        for child in [2 * i + 1, 2 * i + 2] {
+            if child < heap.len() && beats(&heap[worst], &heap[child]) {
+                worst = child;
+            }
+        }
+
+        if worst == i {
+            break;
+        }
+        heap.swap(worst, i);
+        i = worst;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::select_from;
+
+    // deterministic, shuffled looking values
+    fn scrambled(n: u64) -> Vec<u64> {
+        (0..n).map(|i| (i * 7919 + 13) % 1009).collect()
+    }
+
+    #[test]
+    fn matches_a_full_sort_within_the_comparison_bound() {
+        let values = scrambled(1000);
+        let k = 10;
+
+        let mut comparisons = 0;
+        let top = select_from(values.clone()).tournament_with(k, |a, b| {
+            comparisons += 1;
+            a.cmp(b)
+        });
+
+        let mut sorted = values;
+        sorted.sort_by(|a, b| b.cmp(a));
+        assert_eq!(top, sorted[..k]);
+
+        // n * (1 + 2 * ceil(log2 k)) for the heap, plus k * 2 * ceil(log2 k) for the ranking
+        let log_k = k.next_power_of_two().trailing_zeros() as usize;
+        assert!(comparisons <= 1000 * (1 + 2 * log_k) + k * 2 * log_k);
+    }
+
+    #[test]
+    fn ties_keep_original_order() {
+        let people = vec![("ann", 2), ("bob", 3), ("cid", 2), ("dee", 2)];
+
+        let top = select_from(people).tournament_with(3, |a, b| a.1.cmp(&b.1));
+        assert_eq!(top, [("bob", 3), ("ann", 2), ("cid", 2)]);
+    }
+
+    #[test]
+    fn inconsistent_comparisons_still_rank() {
+        // answers that depend on the order they're asked in, with no total order behind them
+        let values: Vec<u64> = (0..50).collect();
+        let top = select_from(values).tournament_with(20, |a, b| (a * 31 + b * 17).cmp(&800));
+
+        assert_eq!(top.len(), 20);
+        let mut unique = top.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), 20);
+    }
+
+    #[test]
+    fn small_and_empty_selections() {
+        assert!(select_from(vec![1, 2])
+            .tournament_with(0, |a, b| a.cmp(b))
+            .is_empty());
+        assert_eq!(
+            select_from(vec![2, 3, 1]).tournament_with(10, |a, b| a.cmp(b)),
+            [3, 2, 1]
+        );
+        assert!(select_from(Vec::<u8>::new())
+            .tournament_with(3, |a, b| a.cmp(b))
+            .is_empty());
+    }
+}
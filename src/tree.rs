@@ -0,0 +1,191 @@
+//! Selection trees, where each chosen value can open up a new selection. See [select_tree_from](crate::select_tree_from).
+
+use crate::error::ChooseError;
+use crate::{select_from, Choice};
+
+pub use crate::error::DepthExceeded;
+
+/// The depth [TreeSelector] allows by default, before giving up with [DepthExceeded].
+pub const DEFAULT_MAX_DEPTH: usize = 64;
+
+/// A value that may expand into further choices once it is chosen.
+pub trait Expand: Sized {
+    /// The choices opened up by choosing this value, or `None` if this value is a leaf.
+    fn children(&self) -> Option<Vec<Self>>;
+}
+
+/// Wraps the roots of a selection tree, see [select_tree_from](crate::select_tree_from).
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct TreeSelector<T> {
+    roots: Vec<T>,
+    max_depth: usize,
+}
+
+impl<T: Expand> TreeSelector<T> {
+    pub(crate) fn with_roots(roots: Vec<T>) -> TreeSelector<T> {
+        TreeSelector {
+            roots,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Sets the maximum number of levels that will be chosen from, defaults to
+    /// [DEFAULT_MAX_DEPTH].
+    pub fn max_depth(self, max_depth: usize) -> TreeSelector<T> {
+        TreeSelector { max_depth, ..self }
+    }
+
+    /// Walks down the tree, using `chooser` to choose one value at each level. The chooser also
+    /// gets the values chosen so far (the current path). Choosing stops once a leaf is reached
+    /// (or a value with no children), and the full chosen path is returned.
+    /// ```
+    /// use choose_from::select_tree_from;
+    /// use choose_from::tree::Expand;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum Item {
+    ///     Recipe(&'static str),
+    ///     Material(&'static str),
+    /// }
+    ///
+    /// impl Expand for Item {
+    ///     fn children(&self) -> Option<Vec<Item>> {
+    ///         match self {
+    ///             Item::Recipe(_) => Some(vec![Item::Material("iron"), Item::Material("wood")]),
+    ///             Item::Material(_) => None,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let path = select_tree_from(vec![Item::Recipe("axe")])
+    ///     .with(|_path, mut choices| choices.pop().unwrap())
+    ///     .unwrap();
+    ///
+    /// assert_eq!(path, [Item::Recipe("axe"), Item::Material("wood")]);
+    /// ```
+    pub fn with<C>(self, mut chooser: C) -> Result<Vec<T>, ChooseError>
+    where
+        C: for<'guard> FnMut(&[T], Vec<Choice<'guard, T>>) -> Choice<'guard, T>,
+    {
+        let mut path = Vec::new();
+        let mut level = self.roots;
+
+        while !level.is_empty() {
+            if path.len() == self.max_depth {
+                return Err(DepthExceeded {
+                    max_depth: self.max_depth,
+                }
+                .into());
+            }
+
+            // each level gets its own guard scope
+            let chosen = select_from(level).one_with(|choices| chooser(&path, choices));
+            let children = chosen.children();
+            path.push(chosen);
+
+            match children {
+                Some(children) => level = children,
+                None => break,
+            }
+        }
+
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::select_tree_from;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Node {
+        name: &'static str,
+        depth: usize,
+    }
+
+    // a tree three levels deep, where every node has two children
+    impl Expand for Node {
+        fn children(&self) -> Option<Vec<Node>> {
+            (self.depth < 2).then(|| {
+                vec![
+                    Node {
+                        name: "left",
+                        depth: self.depth + 1,
+                    },
+                    Node {
+                        name: "right",
+                        depth: self.depth + 1,
+                    },
+                ]
+            })
+        }
+    }
+
+    #[test]
+    fn three_level_path() {
+        let roots = vec![Node {
+            name: "root",
+            depth: 0,
+        }];
+        let mut seen_paths = Vec::new();
+
+        let path = select_tree_from(roots)
+            .with(|path, mut choices| {
+                seen_paths.push(path.len());
+                // alternate between left and right
+                if path.len() % 2 == 0 {
+                    choices.remove(choices.len() - 1)
+                } else {
+                    choices.remove(0)
+                }
+            })
+            .unwrap();
+
+        let names: Vec<_> = path.iter().map(|n| n.name).collect();
+        assert_eq!(names, ["root", "left", "right"]);
+        assert_eq!(seen_paths, [0, 1, 2]);
+    }
+
+    #[derive(Debug)]
+    struct Cycle;
+
+    impl Expand for Cycle {
+        fn children(&self) -> Option<Vec<Cycle>> {
+            Some(vec![Cycle])
+        }
+    }
+
+    #[test]
+    fn depth_limit_stops_cycles() {
+        let result = select_tree_from(vec![Cycle])
+            .max_depth(5)
+            .with(|_, mut choices| choices.pop().unwrap());
+
+        assert_eq!(
+            result.unwrap_err().as_depth_exceeded(),
+            Some(&DepthExceeded { max_depth: 5 })
+        );
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Leaf(u8);
+
+    impl Expand for Leaf {
+        fn children(&self) -> Option<Vec<Leaf>> {
+            None
+        }
+    }
+
+    #[test]
+    fn single_root_single_leaf() {
+        let path = select_tree_from(vec![Leaf(7)])
+            .max_depth(1)
+            .with(|path, mut choices| {
+                assert!(path.is_empty());
+                choices.pop().unwrap()
+            });
+
+        assert_eq!(path, Ok(vec![Leaf(7)]));
+    }
+}
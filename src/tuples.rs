@@ -0,0 +1,98 @@
+// Tuple-shaped versions of `with`, for when the positions of the chosen values mean
+// something. They only convert between tuples and arrays around the array `with`, so all
+// of its guarantees carry over.
+
+use crate::{Choice, Selector, SelectorFixed};
+
+// expands to `$sub`, once for every `$_t` it is repeated over
+macro_rules! replace {
+    ($_t:tt, $sub:ty) => {
+        $sub
+    };
+}
+
+macro_rules! tuple_selections {
+    ($($(#[$attr:meta])* $name:ident => $k:literal ($($c:ident),+);)+) => {
+        impl<I, T> Selector<I, T>
+        where
+            I: IntoIterator<Item = T>,
+        {
+            $(
+                #[doc = concat!(
+                    "Like [with](Selector::with), but `chooser` returns a tuple of ", $k,
+                    " choices instead of an array, and the chosen values are returned as a tuple."
+                )]
+                $(#[$attr])*
+                pub fn $name<C>(self, chooser: C) -> ($(replace!($c, T),)+)
+                where
+                    C: FnOnce(Vec<Choice<'_, T>>) -> ($(replace!($c, Choice<'_, T>),)+),
+                {
+                    let [$($c),+] = self.with(|choices| {
+                        let ($($c,)+) = chooser(choices);
+                        [$($c),+]
+                    });
+                    ($($c,)+)
+                }
+            )+
+        }
+
+        impl<const N: usize, T> SelectorFixed<N, T> {
+            $(
+                #[doc = concat!(
+                    "Like [with](SelectorFixed::with), but `chooser` returns a tuple of ", $k,
+                    " choices instead of an array, see [Selector::", stringify!($name), "]."
+                )]
+                pub fn $name<C>(self, chooser: C) -> ($(replace!($c, T),)+)
+                where
+                    C: FnOnce([Choice<'_, T>; N]) -> ($(replace!($c, Choice<'_, T>),)+),
+                {
+                    let [$($c),+] = self.with(|choices| {
+                        let ($($c,)+) = chooser(choices);
+                        [$($c),+]
+                    });
+                    ($($c,)+)
+                }
+            )+
+        }
+    };
+}
+
+tuple_selections! {
+    with1 => "1" (a);
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let players = vec!["ann", "bob", "cid"];
+    ///
+    /// let (attacker, defender) = select_from(players).with2(|mut choices| {
+    ///     let cid = choices.pop().unwrap();
+    ///     (cid, choices.remove(0))
+    /// });
+    ///
+    /// assert_eq!(attacker, "cid");
+    /// assert_eq!(defender, "ann");
+    /// ```
+    with2 => "2" (a, b);
+    /// ```
+    /// use choose_from::{select_from, select_from_fixed};
+    ///
+    /// let (low, mid, high) = select_from(vec![5, 1, 9, 3]).with3(|mut choices| {
+    ///     choices.sort_by_key(|c| **c);
+    ///     choices.truncate(3);
+    ///     let high = choices.pop().unwrap();
+    ///     let mid = choices.pop().unwrap();
+    ///     (choices.pop().unwrap(), mid, high)
+    /// });
+    ///
+    /// assert_eq!((low, mid, high), (1, 3, 5));
+    ///
+    /// let (x, y, z) = select_from_fixed(['x', 'y', 'z']).with3(|[x, y, z]| (z, y, x));
+    /// assert_eq!((x, y, z), ('z', 'y', 'x'));
+    /// ```
+    with3 => "3" (a, b, c);
+    with4 => "4" (a, b, c, d);
+    with5 => "5" (a, b, c, d, e);
+    with6 => "6" (a, b, c, d, e, f);
+    with7 => "7" (a, b, c, d, e, f, g);
+    with8 => "8" (a, b, c, d, e, f, g, h);
+}
@@ -0,0 +1,75 @@
+//! Choosing from values paired with weights, see
+//! [select_from_weighted](crate::select_from_weighted).
+
+use crate::choice::WeightedChoice;
+use crate::Selector;
+
+/// Wraps choices that each come paired with a weight. The chooser is handed
+/// [WeightedChoice]s, which show the value and the weight separately, and the chosen
+/// values are returned still paired with their weights.
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct WeightedSelector<I, T, W>
+where
+    I: IntoIterator<Item = (T, W)>,
+{
+    selector: Selector<I, (T, W)>,
+}
+
+impl<I, T, W> WeightedSelector<I, T, W>
+where
+    I: IntoIterator<Item = (T, W)>,
+{
+    pub(crate) fn with_choices(choices: I) -> WeightedSelector<I, T, W> {
+        WeightedSelector {
+            selector: Selector::with_choices(choices),
+        }
+    }
+
+    /// The function `chooser` is used to choose from the weighted choices by returning a
+    /// K-selection of them, and the chosen values are returned with their weights, see
+    /// [Selector::with].
+    /// ```
+    /// use choose_from::select_from_weighted;
+    ///
+    /// let odds = vec![("heads", 0.5), ("tails", 0.5), ("edge", 0.001)];
+    ///
+    /// let [unlikely] = select_from_weighted(odds).with(|choices| {
+    ///     let lowest = choices.into_iter().min_by(|a, b| a.weight().total_cmp(b.weight()));
+    ///     [lowest.unwrap()]
+    /// });
+    ///
+    /// assert_eq!(unlikely, ("edge", 0.001));
+    /// ```
+    pub fn with<const K: usize, C>(self, chooser: C) -> [(T, W); K]
+    where
+        C: FnOnce(Vec<WeightedChoice<'_, T, W>>) -> [WeightedChoice<'_, T, W>; K],
+    {
+        self.selector.with(|choices| {
+            chooser(choices.into_iter().map(WeightedChoice::new).collect())
+                .map(WeightedChoice::into_choice)
+        })
+    }
+
+    /// Like [with](WeightedSelector::with), but for choosing any number of choices.
+    /// ```
+    /// use choose_from::select_from_weighted;
+    ///
+    /// let tasks = vec![("email", 1), ("deploy", 5), ("review", 3)];
+    ///
+    /// let urgent = select_from_weighted(tasks)
+    ///     .any_with(|choices| choices.into_iter().filter(|c| *c.weight() >= 3).collect());
+    ///
+    /// assert_eq!(urgent, [("deploy", 5), ("review", 3)]);
+    /// ```
+    pub fn any_with<C>(self, chooser: C) -> Vec<(T, W)>
+    where
+        C: FnOnce(Vec<WeightedChoice<'_, T, W>>) -> Vec<WeightedChoice<'_, T, W>>,
+    {
+        self.selector.any_with(|choices| {
+            chooser(choices.into_iter().map(WeightedChoice::new).collect())
+                .into_iter()
+                .map(WeightedChoice::into_choice)
+                .collect()
+        })
+    }
+}
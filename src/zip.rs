@@ -0,0 +1,139 @@
+//! Joint selections from two pools of choices, see [zip](crate::selector::Selector::zip).
+
+use crate::postcondition::postcondition;
+use crate::{Choice, Guard, Selector};
+
+/// Wraps two pools of choices that are chosen from in a single decision, see
+/// [zip](crate::selector::Selector::zip).
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct ZipSelector<A, B> {
+    left: Vec<A>,
+    right: Vec<B>,
+}
+
+impl<A, B> ZipSelector<A, B> {
+    pub(crate) fn with_pools(left: Vec<A>, right: Vec<B>) -> ZipSelector<A, B> {
+        ZipSelector { left, right }
+    }
+
+    /// The function `chooser` is given the choices of both pools, each under its own guard,
+    /// and must return one choice from each. A choice from one pool can't be returned in the
+    /// other pool's place:
+    /// ```compile_fail
+    /// use choose_from::select_from;
+    ///
+    /// let (weapon, ammo) = select_from(vec!["bow", "sling"])
+    ///     .zip(select_from(vec![1, 2]))
+    ///     .with(|mut weapons, _| (weapons.remove(0), weapons.remove(0)));
+    /// ```
+    /// Not even when both pools hold the same type:
+    /// ```compile_fail
+    /// use choose_from::select_from;
+    ///
+    /// let (first, second) = select_from(vec![1, 2])
+    ///     .zip(select_from(vec![10, 20]))
+    ///     .with(|mut left, _| (left.remove(0), left.remove(0)));
+    /// ```
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// // (weapon, ammo it takes)
+    /// let weapons = vec![("bow", "arrow"), ("sling", "stone")];
+    /// let ammo = vec!["stone", "arrow", "bolt"];
+    ///
+    /// let (weapon, ammo) = select_from(weapons)
+    ///     .zip(select_from(ammo))
+    ///     .with(|mut weapons, ammo| {
+    ///         let weapon = weapons.remove(0);
+    ///         let fitting = ammo.into_iter().find(|a| **a == weapon.1).unwrap();
+    ///         (weapon, fitting)
+    ///     });
+    ///
+    /// assert_eq!(weapon, ("bow", "arrow"));
+    /// assert_eq!(ammo, "arrow");
+    /// ```
+    pub fn with<C>(self, chooser: C) -> (A, B)
+    where
+        C: for<'a, 'b> FnOnce(
+            Vec<Choice<'a, A>>,
+            Vec<Choice<'b, B>>,
+        ) -> (Choice<'a, A>, Choice<'b, B>),
+    {
+        let _guards = (Guard, Guard);
+        let (left, right) = self.into_choices(&_guards);
+        let totals = (left.len(), right.len());
+
+        let (a, b) = chooser(left, right);
+        postcondition!(unique_choices([&a], totals.0));
+        postcondition!(unique_choices([&b], totals.1));
+
+        (a.into_inner(), b.into_inner())
+    }
+
+    /// Like [with](ZipSelector::with), but any number of choices can be returned from each pool.
+    /// ```
+    /// use choose_from::select_from;
+    ///
+    /// let (hand, board) = select_from(vec![2, 9, 4])
+    ///     .zip(select_from(['a', 'b']))
+    ///     .any_with(|hand, board| {
+    ///         (hand.into_iter().filter(|c| **c > 3).collect(), board)
+    ///     });
+    ///
+    /// assert_eq!(hand, [9, 4]);
+    /// assert_eq!(board, ['a', 'b']);
+    /// ```
+    pub fn any_with<C>(self, chooser: C) -> (Vec<A>, Vec<B>)
+    where
+        C: for<'a, 'b> FnOnce(
+            Vec<Choice<'a, A>>,
+            Vec<Choice<'b, B>>,
+        ) -> (Vec<Choice<'a, A>>, Vec<Choice<'b, B>>),
+    {
+        let _guards = (Guard, Guard);
+        let (left, right) = self.into_choices(&_guards);
+        let totals = (left.len(), right.len());
+
+        let (a, b) = chooser(left, right);
+        postcondition!(unique_choices(&a, totals.0));
+        postcondition!(unique_choices(&b, totals.1));
+
+        (
+            a.into_iter().map(Choice::into_inner).collect(),
+            b.into_iter().map(Choice::into_inner).collect(),
+        )
+    }
+
+    // each pool gets its own guard, so their choices have different lifetimes even when
+    // both pools hold the same type
+    fn into_choices(self, _guards: &(Guard, Guard)) -> (Vec<Choice<'_, A>>, Vec<Choice<'_, B>>) {
+        (
+            Selector::with_choices(self.left).into_choices(&_guards.0),
+            Selector::with_choices(self.right).into_choices(&_guards.1),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::select_from;
+
+    #[test]
+    fn zip_picks_one_from_each_pool() {
+        let (a, b) = select_from(0..3)
+            .zip(select_from(vec!["x", "y"]))
+            .with(|mut left, mut right| (left.pop().unwrap(), right.remove(0)));
+
+        assert_eq!((a, b), (2, "x"));
+    }
+
+    #[test]
+    fn zip_with_same_types_keeps_pools_apart() {
+        let (a, b) = select_from(vec![1, 2])
+            .zip(select_from(vec![10, 20]))
+            .any_with(|left, mut right| (left, vec![right.pop().unwrap()]));
+
+        assert_eq!(a, [1, 2]);
+        assert_eq!(b, [20]);
+    }
+}
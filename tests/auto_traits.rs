@@ -0,0 +1,121 @@
+// Pins the auto traits of every public wrapper, so a refactor can't silently change them.
+// The wrappers are meant to be exactly as Send/Sync/Unpin/UnwindSafe as the values they hold.
+
+use choose_from::choice::{ChoiceSet, IndexedChoice, LazyChoices, WeightedChoice};
+use choose_from::draft::Draft;
+use choose_from::error::{
+    ChooseError, ChooseErrorKind, Declined, DepthExceeded, DuplicateKey, DuplicateSelection,
+    EmptyChoices, RetriesExhausted, SelectionCountError, UnrelatedPair, WrongGroup, WrongLength,
+    WrongSelectionCount,
+};
+use choose_from::fixed::SelectorFixed;
+use choose_from::frozen::{FrozenSelector, Picker, PresentationAttestation};
+use choose_from::groups::{GridSelector, GroupedSelector};
+use choose_from::indices::IndexSelector;
+use choose_from::limits::{LimitedChoice, LimitedSelector};
+use choose_from::mapped::{MappedSelector, MappedSelectorFixed};
+use choose_from::nested::NestedSelector;
+use choose_from::nonempty::NonEmptySelector;
+use choose_from::selector::Selector;
+use choose_from::swipe::{Swipe, SwipeState};
+use choose_from::tree::TreeSelector;
+use choose_from::weighted::WeightedSelector;
+use choose_from::zip::ZipSelector;
+use choose_from::Choice;
+use static_assertions::{assert_impl_all, assert_not_impl_any};
+use std::cell::Cell;
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::rc::Rc;
+
+// a value that is Send but not Sync
+type SendOnly = Cell<u8>;
+
+// Choice only pretends to borrow the guard, which is Send and Sync, so it follows T
+assert_impl_all!(Choice<'static, u8>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(Choice<'static, std::sync::Arc<u8>>: Send, Sync);
+assert_not_impl_any!(Choice<'static, Rc<u8>>: Send, Sync);
+assert_impl_all!(Choice<'static, SendOnly>: Send);
+assert_not_impl_any!(Choice<'static, SendOnly>: Sync, RefUnwindSafe);
+assert_impl_all!(Choice<'static, *const u8>: Unpin);
+// an indexed choice really borrows its value from the selector, so it follows &T
+assert_impl_all!(IndexedChoice<'static, u8>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_not_impl_any!(IndexedChoice<'static, SendOnly>: Send, Sync, RefUnwindSafe);
+assert_not_impl_any!(IndexedChoice<'static, Rc<u8>>: Send, Sync);
+// lazy choices only add the iterator they pull from
+assert_impl_all!(LazyChoices<'static, std::vec::IntoIter<u8>>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_not_impl_any!(LazyChoices<'static, std::vec::IntoIter<Rc<u8>>>: Send, Sync);
+// a choice set is only a Vec of choices
+assert_impl_all!(ChoiceSet<'static, u8>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_not_impl_any!(ChoiceSet<'static, Rc<u8>>: Send, Sync);
+
+// the selectors own their choices and nothing else
+assert_impl_all!(Selector<Vec<u8>, u8>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(Selector<Vec<SendOnly>, SendOnly>: Send, Unpin);
+assert_not_impl_any!(Selector<Vec<SendOnly>, SendOnly>: Sync);
+assert_not_impl_any!(Selector<Vec<Rc<u8>>, Rc<u8>>: Send, Sync);
+assert_impl_all!(SelectorFixed<2, u8>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_not_impl_any!(SelectorFixed<2, Rc<u8>>: Send, Sync);
+// cloning a selector only needs its choices to be clonable
+assert_impl_all!(Selector<std::ops::Range<u8>, u8>: Clone);
+assert_not_impl_any!(Selector<std::vec::IntoIter<String>, String>: Copy);
+assert_impl_all!(SelectorFixed<2, u8>: Clone, Copy);
+assert_impl_all!(SelectorFixed<2, String>: Clone);
+assert_not_impl_any!(SelectorFixed<2, String>: Copy);
+assert_impl_all!(IndexSelector<'static, u8>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_not_impl_any!(IndexSelector<'static, SendOnly>: Send, Sync);
+assert_impl_all!(MappedSelector<u8, u8>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(MappedSelectorFixed<2, u8, u8>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_not_impl_any!(MappedSelectorFixed<2, u8, Rc<u8>>: Send, Sync);
+assert_impl_all!(FrozenSelector<u8>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(GroupedSelector<&str, u8>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_not_impl_any!(GroupedSelector<&str, Rc<u8>>: Send, Sync);
+assert_impl_all!(GridSelector<2, 3, u8>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_not_impl_any!(GridSelector<2, 3, Rc<u8>>: Send, Sync);
+assert_impl_all!(ZipSelector<u8, u8>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_not_impl_any!(ZipSelector<u8, Rc<u8>>: Send, Sync);
+assert_impl_all!(WeightedSelector<Vec<(u8, f64)>, u8, f64>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_not_impl_any!(WeightedSelector<Vec<(Rc<u8>, f64)>, Rc<u8>, f64>: Send, Sync);
+assert_impl_all!(NonEmptySelector<u8>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(LimitedSelector<u8>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(Draft<u8>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(TreeSelector<u8>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_not_impl_any!(TreeSelector<Rc<u8>>: Send, Sync);
+// the thunks are held as they are, so they decide along with the outer values
+assert_impl_all!(NestedSelector<u8, fn() -> Vec<u8>>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_not_impl_any!(NestedSelector<u8, Box<dyn FnOnce() -> Vec<u8>>>: Send, Sync);
+
+// a weighted choice only wraps a choice of the pair
+assert_impl_all!(WeightedChoice<'static, u8, f64>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(WeightedChoice<'static, SendOnly, f64>: Send);
+assert_not_impl_any!(WeightedChoice<'static, SendOnly, f64>: Sync);
+
+// a swipe only hands a choice back, so it is exactly as Send and Sync as the choice
+assert_impl_all!(Swipe<'static, u8>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(Swipe<'static, SendOnly>: Send);
+assert_not_impl_any!(Swipe<'static, SendOnly>: Sync);
+assert_impl_all!(SwipeState: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+
+// limited choices share their copy counter, so they are tied to the thread they were made on
+assert_impl_all!(LimitedChoice<'static, u8>: Unpin);
+assert_not_impl_any!(LimitedChoice<'static, u8>: Send, Sync);
+
+assert_impl_all!(Picker: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(PresentationAttestation: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+
+// errors are plain data, so they can be boxed into a `dyn Error + Send + Sync`
+assert_impl_all!(ChooseError: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(ChooseErrorKind: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(WrongSelectionCount: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(SelectionCountError: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(UnrelatedPair: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(WrongLength: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(WrongGroup: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(DepthExceeded: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(RetriesExhausted<String>: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_not_impl_any!(RetriesExhausted<Rc<str>>: Send, Sync);
+// except for the boxed error a chooser can decline with
+assert_impl_all!(Declined: Send, Sync, Unpin);
+assert_not_impl_any!(Declined: UnwindSafe, RefUnwindSafe);
+assert_impl_all!(DuplicateSelection: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(DuplicateKey: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(EmptyChoices: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
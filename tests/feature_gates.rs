@@ -0,0 +1,169 @@
+// Checks which public items exist under which features. The core probe only uses core items
+// and is compiled in every feature configuration, so if a core item ever started depending on
+// a feature item, building this with `--no-default-features` would fail.
+
+mod core_probe {
+    use choose_from::choice::{self, Choice};
+    use choose_from::draft::Draft;
+    use choose_from::error::{
+        ChooseError, ChooseErrorKind, SelectionCountError, UnrelatedPair, WrongSelectionCount,
+    };
+    use choose_from::fixed::SelectorFixed;
+    use choose_from::frozen::{FrozenSelector, Picker, PresentationAttestation};
+    use choose_from::indices::IndexSelector;
+    use choose_from::limits::{LimitedChoice, LimitedSelector};
+    use choose_from::mapped::MappedSelector;
+    use choose_from::nested::NestedSelector;
+    use choose_from::nonempty::NonEmptySelector;
+    use choose_from::selector::Selector;
+    use choose_from::swipe::{Swipe, SwipeState};
+    use choose_from::tree::{DepthExceeded, Expand, TreeSelector};
+    use choose_from::weighted::WeightedSelector;
+    use choose_from::zip::ZipSelector;
+    use choose_from::{
+        select_from, select_from_fixed, select_from_shared, select_indices_from, select_then,
+        select_tree_from,
+    };
+
+    // naming every public type is enough to check that it exists
+    #[allow(dead_code)]
+    type Nameable<'a> = (
+        Option<Choice<'a, ()>>,
+        Selector<Vec<()>, ()>,
+        SelectorFixed<1, ()>,
+        IndexSelector<'a, ()>,
+        MappedSelector<(), ()>,
+        FrozenSelector<()>,
+        ZipSelector<(), ()>,
+        WeightedSelector<Vec<((), ())>, (), ()>,
+        choice::WeightedChoice<'a, (), ()>,
+        NonEmptySelector<()>,
+        Draft<()>,
+        LimitedSelector<()>,
+        LimitedChoice<'a, ()>,
+        Picker,
+        PresentationAttestation,
+        NestedSelector<(), fn() -> Vec<()>>,
+        Swipe<'a, ()>,
+        SwipeState,
+        TreeSelector<Leaf>,
+        DepthExceeded,
+        WrongSelectionCount,
+        SelectionCountError,
+        UnrelatedPair,
+        ChooseError,
+        ChooseErrorKind,
+    );
+
+    #[derive(Debug, PartialEq)]
+    struct Leaf;
+
+    impl Expand for Leaf {
+        fn children(&self) -> Option<Vec<Leaf>> {
+            None
+        }
+    }
+
+    #[test]
+    fn core_items_exist() {
+        let chosen = select_from(vec![1, 2, 3]).any_with(|c| choice::retain(c, |n| *n > 1));
+        assert_eq!(chosen, [2, 3]);
+
+        let [one] = select_from_fixed([1, 2]).with(|[one, _]| [one]);
+        assert_eq!(one, 1);
+
+        assert_eq!(
+            select_from_shared(&[std::sync::Arc::new(1)])
+                .any_with(|c| c)
+                .len(),
+            1
+        );
+        assert_eq!(select_indices_from(&[1, 2]).any_with(|c| c), [0, 1]);
+
+        let empty: fn() -> Vec<()> = Vec::new;
+        assert_eq!(
+            select_then(vec![((), empty)]).with(|mut c| c.remove(0), |c| c),
+            ((), vec![])
+        );
+
+        assert_eq!(
+            select_tree_from(vec![Leaf]).with(|_, mut c| c.remove(0)),
+            Ok(vec![Leaf])
+        );
+    }
+
+    #[test]
+    fn fixed_arithmetic_falls_back() {
+        // the same signatures in every feature configuration, so enabling `nightly` can't
+        // break code written against stable
+        let both: Selector<Vec<i32>, i32> = select_from_fixed([1]).concat(select_from_fixed([2]));
+        assert_eq!(both.any_with(|c| c), [1, 2]);
+
+        let (kept, rest): ([i32; 1], Vec<i32>) =
+            select_from_fixed([1, 2, 3]).with_rest(|[_, two, _]| [two]);
+        assert_eq!((kept, rest), ([2], vec![1, 3]));
+
+        let pool: Vec<i32> = select_from_fixed([1, 2]).reject_with(|[one, _]| [one]);
+        assert_eq!(pool, [2]);
+
+        let (first, rest): (Selector<Vec<i32>, i32>, Selector<Vec<i32>, i32>) =
+            select_from_fixed([1, 2, 3]).split::<1>();
+        assert_eq!(first.any_with(|c| c), [1]);
+        assert_eq!(rest.any_with(|c| c), [2, 3]);
+    }
+}
+
+#[cfg(feature = "nightly")]
+mod nightly_probe {
+    use choose_from::owned::NotShared;
+    use choose_from::{select_from, select_from_fixed};
+    use std::sync::Mutex;
+
+    #[test]
+    fn fixed_arithmetic_stays_fixed() {
+        // the chooser destructures exactly three choices, which only compiles if the length
+        // is still known at compile time
+        let both = select_from_fixed([1]).concat_fixed(select_from_fixed([2, 3]));
+
+        assert_eq!(both.with(|[a, b, c]| [c, b, a]), [3, 2, 1]);
+    }
+
+    fn assert_not_shared<T: NotShared>() {}
+
+    #[test]
+    fn owned_interior_mutability_is_not_shared() {
+        assert_not_shared::<Mutex<Vec<u8>>>();
+        assert_not_shared::<Box<std::cell::RefCell<u8>>>();
+        assert_not_shared::<&mut u8>();
+
+        let units = vec![Mutex::new(10), Mutex::new(20)];
+        let [chosen] = select_from(units)
+            .assert_no_shared_handles()
+            .with(|mut choices| {
+                *choices[0].lock().unwrap() -= 5;
+                [choices.remove(0)]
+            });
+
+        assert_eq!(chosen.into_inner().unwrap(), 5);
+    }
+}
+
+#[cfg(feature = "rand")]
+mod rand_probe {
+    use choose_from::{select_from, select_from_fixed};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn random_choosers_exist() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let [one] = select_from(vec![1]).choose_random(&mut rng).unwrap();
+        assert_eq!(one, 1);
+        assert_eq!(select_from_fixed([2]).choose_random(&mut rng), [2]);
+        assert_eq!(
+            select_from(vec![3]).sample_stratified(|n| *n, &mut rng),
+            Ok([3])
+        );
+    }
+}